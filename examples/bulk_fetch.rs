@@ -0,0 +1,20 @@
+use std::env;
+
+use modark::Client;
+
+fn main() {
+    let client = Client::new(
+        env::var("MODARCH_KEY").expect("Expected a Mod Archive API key in the environment variables")
+    );
+
+    // every request made through the same client shares one connection pool, so fetching
+    // many modules in a row doesn't open a new connection each time
+    let started = std::time::Instant::now();
+    for mod_id in [51772, 41070, 61772, 99356, 88676] {
+        match client.get(mod_id) {
+            Ok(modinfo) => println!("{}: {}", mod_id, modinfo.filename),
+            Err(e) => eprintln!("{}: {}", mod_id, e),
+        }
+    }
+    println!("Fetched in {:?}", started.elapsed());
+}
@@ -0,0 +1,14 @@
+use std::env;
+
+use modark::Client;
+
+fn main() {
+    let client = Client::new(
+        env::var("MODARCH_KEY").expect("Expected a Mod Archive API key in the environment variables")
+    );
+
+    let modinfo = client.get(51772).unwrap();
+    println!("{:#?}", modinfo);
+
+    println!("{}", client.track_requests().unwrap());
+}
@@ -0,0 +1,18 @@
+use std::env;
+
+use modark::ModInfo;
+
+fn main() {
+    let key = &env::var("MODARCH_KEY")
+        .expect("Expected a Mod Archive API key in the environment variables");
+    let dir = env::temp_dir().join("modark_noway");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // grab every version of the song, one that fails doesn't stop the others
+    for download in ModInfo::download_search_results("noway.s3m", &dir, key).unwrap() {
+        match download {
+            Ok(path) => println!("Saved {}", path.display()),
+            Err(e) => eprintln!("Couldn't download one of them: {}", e),
+        }
+    }
+}
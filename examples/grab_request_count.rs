@@ -1,6 +1,6 @@
 use std::env;
 
-use modark::ModInfo;
+use modark::{ Error, ModInfo };
 
 fn main() {
     let key = &env::var("MODARCH_KEY")
@@ -9,6 +9,9 @@ fn main() {
 
     match request_count {
         Ok(count) => println!("{}", count),
-        Err(_) => eprintln!("Error"),
+        Err(Error::InvalidApiKey) => eprintln!("The API key is wrong or has expired"),
+        // the outer error has the API key redacted, the ureq error inside it doesn't
+        Err(e @ Error::APIRequestError(_)) => eprintln!("Couldn't reach Mod Archive: {}", e),
+        Err(e) => eprintln!("Error: {}", e),
     };
 }
@@ -0,0 +1,11 @@
+use std::env;
+
+use modark::ModInfo;
+
+fn main() {
+    let key = &env::var("MODARCH_KEY")
+        .expect("Expected a Mod Archive API key in the environment variables");
+    // a search result only has the ID, filename and title, get() fetches the rest of it
+    let modinfo = ModInfo::resolve_filename("noway.s3m").unwrap()[0].get(key).unwrap();
+    println!("{:#?}", modinfo);
+}
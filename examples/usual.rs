@@ -1,20 +1,18 @@
 use std::env;
 
-use modark::ModInfo;
+use modark::ModRef;
 
 fn main() {
     let key = &env::var("MODARCH_KEY")
         .expect("Expected a Mod Archive API key in the environment variables");
     let args: Vec<String> = std::env::args().collect();
+    let mod_ref: Option<ModRef> = args.get(2).map(|arg| arg.parse().unwrap());
     match args.get(1).unwrap_or(&"".into()).as_ref() {
         "get" => {
-            let mod_id = ModInfo::resolve_filename(
-                args.get(2)
-                    .expect("No filename provided as second argument."),
-            )
-            .unwrap()[0]
-                .id;
-            let mod_info = ModInfo::get(mod_id, key).unwrap();
+            let mod_info = mod_ref
+                .expect("No module ID or filename provided as second argument.")
+                .resolve(key)
+                .unwrap();
             println!("{}", &mod_info.instrument_text);
 
             println!("\n----------------------------------------\n");
@@ -26,13 +24,10 @@ fn main() {
             println!("Download link: {}", mod_info.get_download_link());
         }
         "download" => {
-            let mod_id = ModInfo::resolve_filename(
-                args.get(2)
-                    .expect("No filename provided as second argument."),
-            )
-            .unwrap()[0]
-                .id;
-            let mod_info = ModInfo::get(mod_id, key).unwrap();
+            let mod_info = mod_ref
+                .expect("No module ID or filename provided as second argument.")
+                .resolve(key)
+                .unwrap();
 
             let module_bytes = match mod_info.download_module() {
                 Ok(module_bytes) => module_bytes,
@@ -44,6 +39,6 @@ fn main() {
 
             println!("Raw bytes:\n{:?}", module_bytes);
         }
-        _ => println!("Usage: trackermeta get <filename>"),
+        _ => println!("Usage: trackermeta get <module ID or filename>"),
     }
 }
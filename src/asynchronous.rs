@@ -0,0 +1,303 @@
+use crate::search::parse_module_list;
+use crate::{
+    check_api_key,
+    download_link_on,
+    looks_like_html,
+    percent_encode,
+    redact_key,
+    search_page_url,
+    ModInfo,
+    ModSearch,
+    RequestQuota,
+    SearchResults,
+    SearchType,
+    BASEURL,
+    DOWNLOADURL,
+    MAX_MODULE_SIZE,
+    MAX_PAGE_SIZE,
+    MAX_REDIRECTS,
+    USER_AGENT,
+};
+
+/// Identical to [`Client`](crate::Client), except every request returns a future instead of
+/// blocking the thread it's called on (only here with the `async` feature enabled). It uses
+/// `reqwest` so it has to run inside a tokio runtime, the parsing is shared with the blocking
+/// functions so you get back the exact same structs. Unlike the blocking client it asks for
+/// deflate compressed responses aswell as gzip ones.
+#[derive(Debug, Clone)]
+pub struct AsyncClient {
+    api_key: String,
+    client: reqwest::Client,
+    base_url: String,
+    download_url: String,
+    timeout: std::time::Duration,
+}
+
+impl Default for AsyncClient {
+    /// An async client without an API key, only good for the requests that don't need one
+    /// (resolving filenames and downloading modules)
+    fn default() -> AsyncClient {
+        AsyncClient::new("")
+    }
+}
+
+impl AsyncClient {
+    /// Creates an async client that sends the given API key along with every request.
+    pub fn new(api_key: impl Into<String>) -> AsyncClient {
+        AsyncClient {
+            api_key: api_key.into(),
+            client: reqwest::Client
+                ::builder()
+                .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS as usize))
+                .build()
+                .expect("the TLS backend couldn't be set up"),
+            base_url: BASEURL.into(),
+            download_url: DOWNLOADURL.into(),
+            timeout: std::time::Duration::from_secs(60),
+        }
+    }
+
+    /// Sends the XML API requests somewhere else instead of Mod Archive, handy for tests or
+    /// a mirror.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> AsyncClient {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Downloads modules from somewhere else, see
+    /// [`Client::with_download_url()`](crate::Client::with_download_url).
+    pub fn with_download_url(mut self, download_url: impl Into<String>) -> AsyncClient {
+        self.download_url = download_url.into();
+        self
+    }
+
+    /// Changes how long a request can take before it gives up, the default is 60 seconds.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> AsyncClient {
+        self.timeout = timeout;
+        self
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    async fn _call(&self, url: &str) -> Result<reqwest::Response, crate::Error> {
+        log::debug!("GET {}", redact_key(url));
+        let response = self.client
+            .get(url)
+            .timeout(self.timeout)
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .send().await;
+
+        match response.and_then(|response| response.error_for_status()) {
+            Ok(response) => {
+                log::debug!("{} answered with {}", redact_key(url), response.status());
+                Ok(response)
+            }
+            Err(e) => {
+                log::warn!("GET {} failed: {}", redact_key(url), redact_key(&e.to_string()));
+                Err(crate::Error::AsyncRequestError(e))
+            }
+        }
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    async fn _text(&self, url: &str) -> Result<String, crate::Error> {
+        let response = match self._call(url).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        match response.text().await {
+            Ok(body) => Ok(body),
+            Err(e) => Err(crate::Error::AsyncRequestError(e)),
+        }
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    async fn _bytes(&self, url: &str, max_bytes: u64) -> Result<Vec<u8>, crate::Error> {
+        let mut response = match self._call(url).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let mut vector_of_bytes = Vec::new();
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => vector_of_bytes.extend_from_slice(&chunk),
+                Ok(None) => {
+                    break;
+                }
+                Err(e) => {
+                    return Err(crate::Error::AsyncRequestError(e));
+                }
+            }
+
+            if (vector_of_bytes.len() as u64) > max_bytes {
+                return Err(crate::Error::SizeLimitExceeded(max_bytes));
+            }
+        }
+
+        Ok(vector_of_bytes)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    async fn _inner_request(
+        &self,
+        request: &str,
+        params: &[(&str, &str)]
+    ) -> Result<String, crate::Error> {
+        match check_api_key(&self.api_key) {
+            Ok(()) => {}
+            Err(e) => {
+                return Err(e);
+            }
+        }
+
+        let (base_url, api_key) = (&self.base_url, &self.api_key);
+        let mut url = format!("{base_url}?key={api_key}&request={request}");
+        for (name, value) in params {
+            url.push_str(&format!("&{name}={}", percent_encode(value)));
+        }
+
+        self._text(&url).await
+    }
+
+    /// Fetches the full [`ModInfo`] of a module using its ID, see [`ModInfo::get_async()`].
+    pub async fn get(&self, mod_id: u32) -> Result<ModInfo, crate::Error> {
+        // module IDs start from 1, so there's no point in asking about 0
+        if mod_id == 0 {
+            return Err(crate::Error::NotFound);
+        }
+
+        let body = match
+            self._inner_request("view_by_moduleid", &[("query", &mod_id.to_string())]).await
+        {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        ModInfo::from_xml(&body, mod_id)
+    }
+
+    /// Runs a search on Mod Archive, see [`ModSearch::search_async()`].
+    pub async fn search(&self, search: &ModSearch) -> Result<SearchResults, crate::Error> {
+        let params = search._params();
+        let params: Vec<(&str, &str)> = params
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
+
+        let body = match self._inner_request("search", &params).await {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        parse_module_list(&body)
+    }
+
+    /// Downloads the raw bytes of a module file, see [`ModInfo::download_async()`].
+    pub async fn download_module(&self, modinfo: &ModInfo) -> Result<Vec<u8>, crate::Error> {
+        self.download_module_with_limit(modinfo, MAX_MODULE_SIZE).await
+    }
+
+    /// Downloads the raw bytes of a module file with your own size limit, see
+    /// [`ModInfo::download_module_with_limit()`].
+    pub async fn download_module_with_limit(
+        &self,
+        modinfo: &ModInfo,
+        max_bytes: u64
+    ) -> Result<Vec<u8>, crate::Error> {
+        let link = download_link_on(&self.download_url, modinfo.id, &modinfo.filename);
+
+        let vector_of_bytes = match self._bytes(&link, max_bytes).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        // an error page instead of the module, don't hand it back as if it were one
+        if looks_like_html(&vector_of_bytes) {
+            return Err(crate::Error::MalformedResponse);
+        }
+
+        Ok(vector_of_bytes)
+    }
+
+    /// Searches for your string on Mod Archive by the given [`SearchType`] and gives you the
+    /// results on the given page, see [`ModInfo::resolve_page()`].
+    pub async fn resolve_page(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        page: u32
+    ) -> Result<SearchResults, crate::Error> {
+        let body = match self._bytes(&search_page_url(query, search_type, page), MAX_PAGE_SIZE).await {
+            Ok(body) => body,
+            Err(e) if e.http_status() == Some(404) => {
+                return Err(crate::Error::NotFound);
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
+        let body = String::from_utf8_lossy(&body);
+
+        ModInfo::_parse_search_page(&body, page)
+    }
+
+    /// Tells you how many requests have been made with this client's API key so far, see
+    /// [`ModInfo::request_quota()`].
+    pub async fn request_quota(&self) -> Result<RequestQuota, crate::Error> {
+        let body = match self._inner_request("view_requests", &[]).await {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        RequestQuota::_parse(&body)
+    }
+}
+
+impl ModInfo {
+    /// Identical to [`ModInfo::get()`], but doesn't block the thread while waiting on Mod Archive.
+    pub async fn get_async(mod_id: u32, api_key: &str) -> Result<ModInfo, crate::Error> {
+        AsyncClient::new(api_key).get(mod_id).await
+    }
+
+    /// Identical to [`ModInfo::download_module()`], but doesn't block the thread while the module
+    /// is downloading.
+    pub async fn download_async(&self) -> Result<Vec<u8>, crate::Error> {
+        AsyncClient::default().download_module(self).await
+    }
+
+    /// Identical to [`ModInfo::resolve_filename_page()`], but doesn't block the thread while
+    /// waiting on Mod Archive.
+    pub async fn resolve_filename_async(
+        filename: &str,
+        page: u32
+    ) -> Result<SearchResults, crate::Error> {
+        AsyncClient::default().resolve_page(filename, SearchType::Filename, page).await
+    }
+
+    /// Identical to [`ModInfo::request_quota()`], but doesn't block the thread while waiting on
+    /// Mod Archive.
+    pub async fn request_quota_async(api_key: &str) -> Result<RequestQuota, crate::Error> {
+        AsyncClient::new(api_key).request_quota().await
+    }
+}
+
+impl ModSearch {
+    /// Identical to [`ModSearch::search()`], but doesn't block the thread while waiting on Mod
+    /// Archive.
+    pub async fn search_async(&self, api_key: &str) -> Result<SearchResults, crate::Error> {
+        AsyncClient::new(api_key).search(self).await
+    }
+}
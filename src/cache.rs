@@ -0,0 +1,70 @@
+use crate::Client;
+use crate::ModInfo;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{ Duration, Instant };
+
+/// Wraps a [`Client`] and remembers the modules it fetched with [`CachedClient::get()`] for a
+/// while, so asking for the same module again doesn't cost another request. It can be shared
+/// between threads (e.g. in an `Arc`), the cache is behind a [`Mutex`].
+#[derive(Debug)]
+pub struct CachedClient {
+    client: Client,
+    ttl: Duration,
+    entries: Mutex<HashMap<u32, (Instant, ModInfo)>>,
+}
+
+impl CachedClient {
+    /// Creates a cache in front of the given client, a module is fetched again once it's been
+    /// in the cache for longer than `ttl`.
+    pub fn new(client: Client, ttl: Duration) -> CachedClient {
+        CachedClient {
+            client,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The client the requests are made with, for everything that isn't cached.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _entries(&self) -> std::sync::MutexGuard<'_, HashMap<u32, (Instant, ModInfo)>> {
+        // a thread panicking while holding the lock can't leave the map half updated
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Identical to [`Client::get()`], but if the module was fetched less than `ttl` ago you get
+    /// the cached one back without making a request. Errors aren't cached.
+    pub fn get(&self, mod_id: u32) -> Result<ModInfo, crate::Error> {
+        if let Some((fetched, modinfo)) = self._entries().get(&mod_id) {
+            if fetched.elapsed() < self.ttl {
+                return Ok(modinfo.clone());
+            }
+        }
+
+        // the lock isn't held while waiting on Mod Archive, so other threads aren't stuck
+        let modinfo = match self.client.get(mod_id) {
+            Ok(modinfo) => modinfo,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        self._entries().insert(mod_id, (Instant::now(), modinfo.clone()));
+        Ok(modinfo)
+    }
+
+    /// Forgets the cached module with the given ID, so the next [`CachedClient::get()`] fetches
+    /// it again.
+    pub fn invalidate(&self, mod_id: u32) {
+        self._entries().remove(&mod_id);
+    }
+
+    /// Forgets every cached module.
+    pub fn clear(&self) {
+        self._entries().clear();
+    }
+}
@@ -0,0 +1,147 @@
+use crate::api_error;
+use crate::find_node_descendants;
+use crate::find_node_text;
+use crate::find_title;
+use crate::parse_xml;
+use crate::Client;
+use crate::ModInfo;
+
+/// The parts of a module you can pick with [`ModInfo::get_fields()`], each one is the
+/// [`ModInfo`] field with the same name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Field {
+    /// `filename`, the name of the module file
+    Filename,
+    /// `title`, the song title
+    Title,
+    /// `size`, the size as Mod Archive shows it (like "482.06KB")
+    Size,
+    /// `size_bytes`, the size in bytes
+    SizeBytes,
+    /// `md5`, the MD5 hash of the module file
+    Md5,
+    /// `format`, the format Mod Archive verified (like "XM")
+    Format,
+    /// `download_count`, how many times the module was downloaded
+    DownloadCount,
+    /// `fav_count`, how many people favourited the module
+    FavCount,
+    /// `channel_count`, how many channels the module has
+    ChannelCount,
+    /// `genre`, the genre as text
+    Genre,
+    /// `upload_date`, when the module was uploaded
+    UploadDate,
+    /// `instrument_text`, the instrument and sample names
+    InstrumentText,
+}
+
+/// Only the parts of a [`ModInfo`] you asked for with [`ModInfo::get_fields()`], the ID is always
+/// there and every field you didn't ask for is [`None`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartialModInfo {
+    pub id: u32,
+    pub filename: Option<String>,
+    pub title: Option<String>,
+    pub size: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub md5: Option<String>,
+    pub format: Option<String>,
+    pub download_count: Option<u32>,
+    pub fav_count: Option<u32>,
+    pub channel_count: Option<u32>,
+    pub genre: Option<String>,
+    pub upload_date: Option<String>,
+    pub instrument_text: Option<String>,
+}
+
+impl PartialModInfo {
+    /// (a helper function to make the code more readable, do not use directly)
+    pub(crate) fn _parse(
+        body: &str,
+        mod_id: u32,
+        fields: &[Field]
+    ) -> Result<PartialModInfo, crate::Error> {
+        let xml = match parse_xml(body) {
+            Ok(xml) => xml,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let xml_descendants: Vec<_> = xml.descendants().collect();
+
+        if let Some(error) = find_node_text(&xml_descendants, "error") {
+            return Err(api_error(&error));
+        }
+
+        let module_descendants = find_node_descendants(&xml_descendants, "module");
+        if module_descendants.is_empty() {
+            return Err(crate::Error::MalformedResponse);
+        }
+
+        let mut partial = PartialModInfo { id: mod_id, ..Default::default() };
+        // only the nodes that were asked for get looked up
+        for field in fields {
+            let text = |tag: &str| find_node_text(&module_descendants, tag).unwrap_or_default();
+            let number = |tag: &str| text(tag).trim().parse::<u32>().unwrap_or_default();
+            match field {
+                Field::Filename => partial.filename = Some(text("filename")),
+                Field::Title => {
+                    partial.title = Some(find_title(&module_descendants).unwrap_or_default());
+                }
+                Field::Size => partial.size = Some(text("size")),
+                Field::SizeBytes => {
+                    // same as ModInfo, the size is only used when there's no byte count
+                    let bytes = find_node_text(&module_descendants, "bytes").unwrap_or_else(|| text("size"));
+                    partial.size_bytes = Some(bytes.trim().parse::<u64>().unwrap_or_default());
+                }
+                Field::Md5 => partial.md5 = Some(text("hash")),
+                Field::Format => partial.format = Some(text("format")),
+                Field::DownloadCount => partial.download_count = Some(number("hits")),
+                Field::FavCount => partial.fav_count = Some(number("favoured")),
+                Field::ChannelCount => partial.channel_count = Some(number("channels")),
+                Field::Genre => partial.genre = Some(text("genretext")),
+                Field::UploadDate => partial.upload_date = Some(text("date")),
+                Field::InstrumentText => partial.instrument_text = Some(text("instruments")),
+            }
+        }
+
+        Ok(partial)
+    }
+}
+
+impl Client {
+    /// Fetches only the given parts of a module, see [`ModInfo::get_fields()`].
+    pub fn get_fields(&self, mod_id: u32, fields: &[Field]) -> Result<PartialModInfo, crate::Error> {
+        // module IDs start from 1, so there's no point in asking about 0
+        if mod_id == 0 {
+            return Err(crate::Error::NotFound);
+        }
+
+        // the body is parsed only once, _parse looks for the error node itself
+        let body = match self._inner_request("view_by_moduleid", &[("query", &mod_id.to_string())]) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        PartialModInfo::_parse(&body, mod_id, fields)
+    }
+}
+
+impl ModInfo {
+    /// Identical to [`ModInfo::get()`], but only the fields you ask for are picked out of the
+    /// response, which adds up when you're going through thousands of modules and only want a
+    /// couple of things from each. It still costs a request like [`ModInfo::get()`] does.
+    pub fn get_fields(
+        mod_id: u32,
+        api_key: &str,
+        fields: &[Field]
+    ) -> Result<PartialModInfo, crate::Error> {
+        Client::new(api_key).get_fields(mod_id, fields)
+    }
+}
@@ -0,0 +1,103 @@
+/// The tracker formats Mod Archive hosts, anything not covered ends up in [`Format::Other`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Format {
+    /// ProTracker and friends
+    Mod,
+    /// FastTracker 2 Extended Module
+    Xm,
+    /// Impulse Tracker
+    It,
+    /// Scream Tracker 3
+    S3m,
+    /// OpenMPT
+    Mptm,
+    /// OctaMED
+    Med,
+    /// MultiTracker
+    Mtm,
+    /// Scream Tracker 2
+    Stm,
+    /// Oktalyzer
+    Okt,
+    /// UltraTracker
+    Ult,
+    /// Farandole Composer
+    Far,
+    /// Composer 669
+    SixSixNine,
+    /// Abyss' Highest eXperience
+    Ahx,
+    /// HivelyTracker
+    Hvl,
+    /// Any other format, holding onto the string Mod Archive gave us
+    Other(String),
+}
+
+impl Format {
+    /// Every format Mod Archive hosts that has its own variant (so everything but
+    /// [`Format::Other`]), in the order they're declared in. Add new variants here too.
+    pub const ALL: &'static [Format] = &[
+        Format::Mod,
+        Format::Xm,
+        Format::It,
+        Format::S3m,
+        Format::Mptm,
+        Format::Med,
+        Format::Mtm,
+        Format::Stm,
+        Format::Okt,
+        Format::Ult,
+        Format::Far,
+        Format::SixSixNine,
+        Format::Ahx,
+        Format::Hvl,
+    ];
+}
+
+impl From<&str> for Format {
+    /// Turns a format string (e.g. `XM`) into a [`Format`], the case doesn't matter.
+    fn from(format: &str) -> Format {
+        match format.trim().to_ascii_uppercase().as_str() {
+            "MOD" => Format::Mod,
+            "XM" => Format::Xm,
+            "IT" => Format::It,
+            "S3M" => Format::S3m,
+            "MPTM" => Format::Mptm,
+            "MED" => Format::Med,
+            "MTM" => Format::Mtm,
+            "STM" => Format::Stm,
+            "OKT" => Format::Okt,
+            "ULT" => Format::Ult,
+            "FAR" => Format::Far,
+            "669" => Format::SixSixNine,
+            "AHX" => Format::Ahx,
+            "HVL" => Format::Hvl,
+            _ => Format::Other(format.trim().to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    /// Writes the format the way Mod Archive does, which is the file extension in uppercase.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match self {
+            Format::Mod => "MOD",
+            Format::Xm => "XM",
+            Format::It => "IT",
+            Format::S3m => "S3M",
+            Format::Mptm => "MPTM",
+            Format::Med => "MED",
+            Format::Mtm => "MTM",
+            Format::Stm => "STM",
+            Format::Okt => "OKT",
+            Format::Ult => "ULT",
+            Format::Far => "FAR",
+            Format::SixSixNine => "669",
+            Format::Ahx => "AHX",
+            Format::Hvl => "HVL",
+            Format::Other(format) => format,
+        };
+        write!(f, "{}", format)
+    }
+}
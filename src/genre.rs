@@ -0,0 +1,327 @@
+/// Every genre Mod Archive sorts modules into as `(genre ID, genre text)`, the text is the same
+/// as what ends up in [`ModInfo::genre`](crate::ModInfo::genre) and the ID is what
+/// [`ModInfo::search_by_genre()`](crate::ModInfo::search_by_genre) takes
+/// ([reference](https://modarchive.org/index.php?request=view_genres))
+pub const GENRES: &[(u32, &str)] = &[
+    (48, "Alternative"),
+    (38, "Gothic"),
+    (103, "Grunge"),
+    (37, "Metal - Extreme"),
+    (36, "Metal (general)"),
+    (35, "Punk"),
+    (54, "Chiptune"),
+    (55, "Demo Style"),
+    (53, "One Hour Compo"),
+    (106, "Chillout"),
+    (2, "Electronic - Ambient"),
+    (9, "Electronic - Breakbeat"),
+    (3, "Electronic - Dance"),
+    (6, "Electronic - Drum & Bass"),
+    (40, "Electronic - Gabber"),
+    (39, "Electronic - Hardcore"),
+    (10, "Electronic - House"),
+    (99, "Electronic - IDM"),
+    (34, "Electronic - Industrial"),
+    (60, "Electronic - Jungle"),
+    (101, "Electronic - Minimal"),
+    (100, "Electronic - Other"),
+    (11, "Electronic - Progressive"),
+    (65, "Electronic - Rave"),
+    (7, "Electronic - Techno"),
+    (5, "Electronic - Trance"),
+    (102, "Trip Hop"),
+    (32, "Funk"),
+    (22, "Hip Hop"),
+    (26, "R & B"),
+    (31, "Soul"),
+    (27, "Jazz - Acid"),
+    (29, "Jazz (general)"),
+    (24, "Swing"),
+    (30, "Blues"),
+    (18, "Country"),
+    (21, "Folk"),
+    (12, "Pop - General"),
+    (62, "Pop - Soft"),
+    (61, "Pop - Synth"),
+    (13, "Rock - General"),
+    (14, "Rock - Hard"),
+    (15, "Rock - Soft"),
+    (20, "Classical"),
+    (50, "Orchestral"),
+    (56, "Ballad"),
+    (45, "Comedy"),
+    (72, "Christmas"),
+    (46, "Experimental"),
+    (52, "Fantasy"),
+    (107, "Easy Listening"),
+    (28, "Medieval"),
+    (44, "New Age"),
+    (59, "Piano"),
+    (58, "Reggae"),
+    (57, "Ska"),
+    (43, "Soundtrack"),
+    (47, "Spiritual"),
+    (8, "Video Game"),
+    (42, "World"),
+    (19, "Oriental"),
+    (23, "Latin"),
+];
+
+/// A genre from Mod Archive's genre list, the ones with subgenres (like `Electronic - Ambient`)
+/// hold onto which subgenre it is, anything not in the list ends up in [`Genre::Other`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Genre {
+    Alternative,
+    Gothic,
+    Grunge,
+    Metal(MetalGenre),
+    Punk,
+    Chiptune,
+    DemoStyle,
+    OneHourCompo,
+    Chillout,
+    Electronic(ElectronicGenre),
+    TripHop,
+    Funk,
+    HipHop,
+    RnB,
+    Soul,
+    Jazz(JazzGenre),
+    Swing,
+    Blues,
+    Country,
+    Folk,
+    Pop(PopGenre),
+    Rock(RockGenre),
+    Classical,
+    Orchestral,
+    Ballad,
+    Comedy,
+    Christmas,
+    Experimental,
+    Fantasy,
+    EasyListening,
+    Medieval,
+    NewAge,
+    Piano,
+    Reggae,
+    Ska,
+    Soundtrack,
+    Spiritual,
+    VideoGame,
+    World,
+    Oriental,
+    Latin,
+    /// Any other genre, holding onto the text Mod Archive gave us
+    Other(String),
+}
+
+/// The subgenres of [`Genre::Electronic`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ElectronicGenre {
+    Ambient,
+    Breakbeat,
+    Dance,
+    DrumAndBass,
+    Gabber,
+    Hardcore,
+    House,
+    Idm,
+    Industrial,
+    Jungle,
+    Minimal,
+    Other,
+    Progressive,
+    Rave,
+    Techno,
+    Trance,
+}
+
+/// The subgenres of [`Genre::Metal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetalGenre {
+    Extreme,
+    General,
+}
+
+/// The subgenres of [`Genre::Jazz`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JazzGenre {
+    Acid,
+    General,
+}
+
+/// The subgenres of [`Genre::Pop`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PopGenre {
+    General,
+    Soft,
+    Synth,
+}
+
+/// The subgenres of [`Genre::Rock`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RockGenre {
+    General,
+    Hard,
+    Soft,
+}
+
+impl Genre {
+    /// Turns the genre text Mod Archive uses (e.g. `Electronic - Ambient`) into a [`Genre`],
+    /// anything it doesn't recognise becomes [`Genre::Other`].
+    pub fn from_text(text: &str) -> Genre {
+        match text.trim() {
+            "Alternative" => Genre::Alternative,
+            "Gothic" => Genre::Gothic,
+            "Grunge" => Genre::Grunge,
+            "Metal - Extreme" => Genre::Metal(MetalGenre::Extreme),
+            "Metal (general)" => Genre::Metal(MetalGenre::General),
+            "Punk" => Genre::Punk,
+            "Chiptune" => Genre::Chiptune,
+            "Demo Style" => Genre::DemoStyle,
+            "One Hour Compo" => Genre::OneHourCompo,
+            "Chillout" => Genre::Chillout,
+            "Electronic - Ambient" => Genre::Electronic(ElectronicGenre::Ambient),
+            "Electronic - Breakbeat" => Genre::Electronic(ElectronicGenre::Breakbeat),
+            "Electronic - Dance" => Genre::Electronic(ElectronicGenre::Dance),
+            "Electronic - Drum & Bass" => Genre::Electronic(ElectronicGenre::DrumAndBass),
+            "Electronic - Gabber" => Genre::Electronic(ElectronicGenre::Gabber),
+            "Electronic - Hardcore" => Genre::Electronic(ElectronicGenre::Hardcore),
+            "Electronic - House" => Genre::Electronic(ElectronicGenre::House),
+            "Electronic - IDM" => Genre::Electronic(ElectronicGenre::Idm),
+            "Electronic - Industrial" => Genre::Electronic(ElectronicGenre::Industrial),
+            "Electronic - Jungle" => Genre::Electronic(ElectronicGenre::Jungle),
+            "Electronic - Minimal" => Genre::Electronic(ElectronicGenre::Minimal),
+            "Electronic - Other" => Genre::Electronic(ElectronicGenre::Other),
+            "Electronic - Progressive" => Genre::Electronic(ElectronicGenre::Progressive),
+            "Electronic - Rave" => Genre::Electronic(ElectronicGenre::Rave),
+            "Electronic - Techno" => Genre::Electronic(ElectronicGenre::Techno),
+            "Electronic - Trance" => Genre::Electronic(ElectronicGenre::Trance),
+            "Trip Hop" => Genre::TripHop,
+            "Funk" => Genre::Funk,
+            "Hip Hop" => Genre::HipHop,
+            "R & B" => Genre::RnB,
+            "Soul" => Genre::Soul,
+            "Jazz - Acid" => Genre::Jazz(JazzGenre::Acid),
+            "Jazz (general)" => Genre::Jazz(JazzGenre::General),
+            "Swing" => Genre::Swing,
+            "Blues" => Genre::Blues,
+            "Country" => Genre::Country,
+            "Folk" => Genre::Folk,
+            "Pop - General" => Genre::Pop(PopGenre::General),
+            "Pop - Soft" => Genre::Pop(PopGenre::Soft),
+            "Pop - Synth" => Genre::Pop(PopGenre::Synth),
+            "Rock - General" => Genre::Rock(RockGenre::General),
+            "Rock - Hard" => Genre::Rock(RockGenre::Hard),
+            "Rock - Soft" => Genre::Rock(RockGenre::Soft),
+            "Classical" => Genre::Classical,
+            "Orchestral" => Genre::Orchestral,
+            "Ballad" => Genre::Ballad,
+            "Comedy" => Genre::Comedy,
+            "Christmas" => Genre::Christmas,
+            "Experimental" => Genre::Experimental,
+            "Fantasy" => Genre::Fantasy,
+            "Easy Listening" => Genre::EasyListening,
+            "Medieval" => Genre::Medieval,
+            "New Age" => Genre::NewAge,
+            "Piano" => Genre::Piano,
+            "Reggae" => Genre::Reggae,
+            "Ska" => Genre::Ska,
+            "Soundtrack" => Genre::Soundtrack,
+            "Spiritual" => Genre::Spiritual,
+            "Video Game" => Genre::VideoGame,
+            "World" => Genre::World,
+            "Oriental" => Genre::Oriental,
+            "Latin" => Genre::Latin,
+            text => Genre::Other(text.to_string()),
+        }
+    }
+
+    /// The genre ID Mod Archive uses for this genre (see [`GENRES`]), [`None`] for
+    /// [`Genre::Other`].
+    pub fn id(&self) -> Option<u32> {
+        let text = self.to_string();
+        GENRES.iter()
+            .find(|(_, genre)| *genre == text)
+            .map(|(id, _)| *id)
+    }
+}
+
+impl std::fmt::Display for Genre {
+    /// Writes the genre the way Mod Archive does, so it matches [`ModInfo::genre`](crate::ModInfo::genre).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Genre::Alternative => "Alternative",
+            Genre::Gothic => "Gothic",
+            Genre::Grunge => "Grunge",
+            Genre::Metal(MetalGenre::Extreme) => "Metal - Extreme",
+            Genre::Metal(MetalGenre::General) => "Metal (general)",
+            Genre::Punk => "Punk",
+            Genre::Chiptune => "Chiptune",
+            Genre::DemoStyle => "Demo Style",
+            Genre::OneHourCompo => "One Hour Compo",
+            Genre::Chillout => "Chillout",
+            Genre::Electronic(ElectronicGenre::Ambient) => "Electronic - Ambient",
+            Genre::Electronic(ElectronicGenre::Breakbeat) => "Electronic - Breakbeat",
+            Genre::Electronic(ElectronicGenre::Dance) => "Electronic - Dance",
+            Genre::Electronic(ElectronicGenre::DrumAndBass) => "Electronic - Drum & Bass",
+            Genre::Electronic(ElectronicGenre::Gabber) => "Electronic - Gabber",
+            Genre::Electronic(ElectronicGenre::Hardcore) => "Electronic - Hardcore",
+            Genre::Electronic(ElectronicGenre::House) => "Electronic - House",
+            Genre::Electronic(ElectronicGenre::Idm) => "Electronic - IDM",
+            Genre::Electronic(ElectronicGenre::Industrial) => "Electronic - Industrial",
+            Genre::Electronic(ElectronicGenre::Jungle) => "Electronic - Jungle",
+            Genre::Electronic(ElectronicGenre::Minimal) => "Electronic - Minimal",
+            Genre::Electronic(ElectronicGenre::Other) => "Electronic - Other",
+            Genre::Electronic(ElectronicGenre::Progressive) => "Electronic - Progressive",
+            Genre::Electronic(ElectronicGenre::Rave) => "Electronic - Rave",
+            Genre::Electronic(ElectronicGenre::Techno) => "Electronic - Techno",
+            Genre::Electronic(ElectronicGenre::Trance) => "Electronic - Trance",
+            Genre::TripHop => "Trip Hop",
+            Genre::Funk => "Funk",
+            Genre::HipHop => "Hip Hop",
+            Genre::RnB => "R & B",
+            Genre::Soul => "Soul",
+            Genre::Jazz(JazzGenre::Acid) => "Jazz - Acid",
+            Genre::Jazz(JazzGenre::General) => "Jazz (general)",
+            Genre::Swing => "Swing",
+            Genre::Blues => "Blues",
+            Genre::Country => "Country",
+            Genre::Folk => "Folk",
+            Genre::Pop(PopGenre::General) => "Pop - General",
+            Genre::Pop(PopGenre::Soft) => "Pop - Soft",
+            Genre::Pop(PopGenre::Synth) => "Pop - Synth",
+            Genre::Rock(RockGenre::General) => "Rock - General",
+            Genre::Rock(RockGenre::Hard) => "Rock - Hard",
+            Genre::Rock(RockGenre::Soft) => "Rock - Soft",
+            Genre::Classical => "Classical",
+            Genre::Orchestral => "Orchestral",
+            Genre::Ballad => "Ballad",
+            Genre::Comedy => "Comedy",
+            Genre::Christmas => "Christmas",
+            Genre::Experimental => "Experimental",
+            Genre::Fantasy => "Fantasy",
+            Genre::EasyListening => "Easy Listening",
+            Genre::Medieval => "Medieval",
+            Genre::NewAge => "New Age",
+            Genre::Piano => "Piano",
+            Genre::Reggae => "Reggae",
+            Genre::Ska => "Ska",
+            Genre::Soundtrack => "Soundtrack",
+            Genre::Spiritual => "Spiritual",
+            Genre::VideoGame => "Video Game",
+            Genre::World => "World",
+            Genre::Oriental => "Oriental",
+            Genre::Latin => "Latin",
+            Genre::Other(text) => text,
+        };
+        write!(f, "{}", text)
+    }
+}
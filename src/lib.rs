@@ -18,27 +18,110 @@
 //! use modark::ModInfo;
 //!
 //! fn main() {
-//!     let modid = ModInfo::resolve_filename("noway.s3m").unwrap()[0].id;
-//!     let modinfo = ModInfo::get(modid).unwrap();
+//!     let modinfo = ModInfo::resolve_one("noway.s3m", "your-api-key").unwrap();
 //!     println!("{:#?}", modinfo);
 //! }
 //! ```
 //!
+//! ## Example
+//! ### Reuse a client for many requests
+//! ```rust
+//! use modark::Client;
+//!
+//! fn main() {
+//!     let client = Client::new("your-api-key");
+//!     for modid in [51772, 41070] {
+//!         println!("{:#?}", client.get(modid).unwrap());
+//!     }
+//! }
+//! ```
+//!
+//! ## Example
+//! ### Fetch module info without blocking (needs the `async` feature)
+//! ```rust
+//! use modark::ModInfo;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let modinfo = ModInfo::get_async(51772, "your-api-key").await.unwrap();
+//!     println!("{:#?}", modinfo);
+//! }
+//! ```
+//!
+//! ## Example
+//! ### Import everything commonly used at once
+//! ```rust
+//! use modark::prelude::*;
+//!
+//! fn main() {
+//!     let client = Client::new("your-api-key");
+//!     let modinfo: ModInfo = client.get(51772).unwrap();
+//!     println!("{}", modinfo.format_enum() == Format::Xm);
+//! }
+//! ```
+//!
 //! There are more examples other than these which showcase more, remember
 //! to check the `examples` directory!
 //!
 //! [Mod Archive]: https://modarchive.org
 #![allow(clippy::needless_doctest_main)]
 
+#[cfg(feature = "async")]
+mod asynchronous;
+mod cache;
+mod fields;
+mod format;
+mod genre;
+mod modref;
+pub mod prelude;
+mod quota;
 mod search;
 
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncClient;
+pub use cache::CachedClient;
+pub use fields::{ Field, PartialModInfo };
+pub use format::Format;
+pub use genre::{ ElectronicGenre, Genre, JazzGenre, MetalGenre, PopGenre, RockGenre, GENRES };
+pub use modref::ModRef;
+pub use quota::QuotaGuard;
+pub use search::ResolveAll;
+
 /// The base URL for the Mod Archive XML API
 const BASEURL: &str = "https://modarchive.org/data/xml-tools.php";
 
-use chrono::prelude::{ DateTime, Utc };
-use std::io::Read;
+/// Where modules get downloaded from by default
+const DOWNLOADURL: &str = "https://api.modarchive.org/downloads.php";
+
+/// The User-Agent sent along with every request, so the site operators can tell who's asking
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
+
+/// How long to wait before the first retry of a failed request, it doubles after each one
+const RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// The most bytes that will be read when downloading a module
+const MAX_MODULE_SIZE: u64 = 64_000_000;
+
+/// The most bytes that will be read from a page scraped off the website, search and module pages
+/// are only a few hundred KB at most
+const MAX_PAGE_SIZE: u64 = 4_000_000;
+
+/// How many redirects a download follows (the download link can send you off to a mirror)
+/// before giving up
+const MAX_REDIRECTS: u32 = 5;
+
+use chrono::prelude::{ DateTime, NaiveDate, NaiveDateTime, Utc };
+use std::io::{ BufRead, Read, Seek };
+use std::path::{ Path, PathBuf };
+use std::sync::OnceLock;
 
-use anyhow::Context;
 use thiserror::Error;
 
 // https://stackoverflow.com/a/64148190
@@ -47,6 +130,285 @@ fn iso8601_time(st: &std::time::SystemTime) -> String {
     format!("{}", dt.format("%+"))
 }
 
+/// Parses a date the way Mod Archive writes them (e.g. `Mon 5th Jul 2004`), ISO 8601 dates are
+/// accepted aswell, anything else gives back [`None`]
+fn parse_modarchive_date(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(date) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    if let Ok(dt) = NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S") {
+        return Some(dt.and_utc());
+    }
+
+    // chrono has no idea what to do with ordinal suffixes, so strip them off the day first
+    let parts: Vec<&str> = date.split_whitespace().collect();
+    match parts.as_slice() {
+        [weekday, day, month, year] => {
+            let day = day.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            NaiveDate::parse_from_str(&format!("{weekday} {day} {month} {year}"), "%a %d %b %Y")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc())
+        }
+        _ => None,
+    }
+}
+
+/// Finds the amount of results in the text a search page shows them in, like
+/// `Showing 1 - 40 of 312 results` or `Results 1-40 of 312`
+fn parse_result_count(text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphanumeric()).replace(',', ""))
+        .collect();
+    let results_at = words.iter().position(|word| word.starts_with("result"))?;
+
+    // "312 results"
+    if let Some(count) = results_at.checked_sub(1).and_then(|i| words[i].parse::<u32>().ok()) {
+        return Some(count);
+    }
+
+    // "results 1-40 of 312"
+    words[results_at..]
+        .windows(2)
+        .find(|pair| pair[0] == "of")
+        .and_then(|pair| pair[1].parse::<u32>().ok())
+}
+
+/// How every agent made by the crate is set up, so one made for a proxy acts the same as the
+/// shared one
+fn agent_builder(timeout: std::time::Duration) -> ureq::AgentBuilder {
+    ureq::AgentBuilder::new().timeout(timeout).user_agent(USER_AGENT).redirects(MAX_REDIRECTS)
+}
+
+/// The agent shared by every request made by the crate, so connections can be pooled and
+/// reused instead of opening a new one each time
+fn shared_agent() -> ureq::Agent {
+    static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+    AGENT.get_or_init(|| {
+        agent_builder(std::time::Duration::from_secs(60)).try_proxy_from_env(true).build()
+    }).clone()
+}
+
+/// (a helper function to make the code more readable, do not use directly)
+fn download_link(id: u32, filename: &str) -> String {
+    download_link_on(DOWNLOADURL, id, filename)
+}
+
+/// (a helper function to make the code more readable, do not use directly)
+fn download_link_on(download_url: &str, id: u32, filename: &str) -> String {
+    format!("{}?moduleid={}#{}", download_url, id, filename)
+}
+
+/// (a helper function to make the code more readable, do not use directly)
+fn module_page_link(id: u32) -> String {
+    format!("https://modarchive.org/index.php?request=view_by_moduleid&query={}", id)
+}
+
+/// Percent-encodes a value so it can be put into a URL as-is, everything but letters, digits and
+/// `-_.~` gets encoded (so spaces, `&` and `#` can't break the URL)
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// The URL of the search page on the Mod Archive website for the given query
+fn search_page_url(query: &str, search_type: SearchType, page: u32) -> String {
+    format!(
+        "https://modarchive.org/index.php?request=search&query={}&submit=Find&search_type={}&page={}",
+        percent_encode(query),
+        search_type.as_str(),
+        page
+    )
+}
+
+/// The URL of a chart page on the Mod Archive website, like `tophits`
+fn chart_page_url(chart: &str, page: u32) -> String {
+    format!("https://modarchive.org/index.php?request=view_chart&query={}&page={}", chart, page)
+}
+
+/// The URL of the page on the Mod Archive website listing the newest uploads
+fn latest_additions_url(page: u32) -> String {
+    format!("https://modarchive.org/index.php?request=view_actions_uploads&page={}", page)
+}
+
+/// Reads a whole module into a vector of bytes, calling `progress` with the amount read so far
+/// (and the total if it's known) after every chunk. Anything bigger than `max_bytes` gives you
+/// an [`Error::SizeLimitExceeded`].
+fn read_module(
+    mut reader: impl Read,
+    max_bytes: u64,
+    total: Option<u64>,
+    mut progress: impl FnMut(u64, Option<u64>)
+) -> Result<Vec<u8>, Error> {
+    let mut vector_of_bytes = Vec::new();
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => {
+                break;
+            }
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                continue;
+            }
+            Err(e) => {
+                return Err(Error::IOError(e));
+            }
+        };
+
+        vector_of_bytes.extend_from_slice(&buffer[..read]);
+        if (vector_of_bytes.len() as u64) > max_bytes {
+            return Err(Error::SizeLimitExceeded(max_bytes));
+        }
+
+        progress(vector_of_bytes.len() as u64, total);
+    }
+
+    Ok(vector_of_bytes)
+}
+
+/// Copies a module straight into `writer` without holding it in memory, giving you the amount
+/// of bytes copied. Anything bigger than `max_bytes` gives you an [`Error::SizeLimitExceeded`]
+/// instead of quietly stopping at the limit, whatever was copied by then is still in `writer`.
+fn copy_module(reader: impl Read, writer: &mut impl std::io::Write, max_bytes: u64) -> Result<u64, Error> {
+    // one byte past the limit is enough to tell the module is too big
+    let copied = match std::io::copy(&mut reader.take(max_bytes.saturating_add(1)), writer) {
+        Ok(copied) => copied,
+        Err(e) => {
+            return Err(Error::IOError(e));
+        }
+    };
+
+    if copied > max_bytes {
+        return Err(Error::SizeLimitExceeded(max_bytes));
+    }
+
+    Ok(copied)
+}
+
+/// Reads a whole web page into a string, anything bigger than `max_bytes` gives you an
+/// [`Error::SizeLimitExceeded`] instead of reading on forever.
+fn read_page(reader: impl Read, max_bytes: u64) -> Result<String, Error> {
+    let bytes = match read_module(reader, max_bytes, None, |_, _| {}) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return Err(e);
+        }
+    };
+
+    match String::from_utf8(bytes) {
+        Ok(page) => Ok(page),
+        Err(e) => Err(Error::IOError(std::io::Error::new(std::io::ErrorKind::InvalidData, e))),
+    }
+}
+
+/// Tells you if the start of a download is an HTML page rather than a module, which is what you
+/// get back when the download link leads to an error page instead of the file
+fn looks_like_html(bytes: &[u8]) -> bool {
+    let start = match bytes.iter().position(|byte| !byte.is_ascii_whitespace()) {
+        Some(start) => &bytes[start..],
+        None => {
+            return false;
+        }
+    };
+
+    [&b"<!doctype html"[..], b"<html"]
+        .iter()
+        .any(|tag| start.len() >= tag.len() && start[..tag.len()].eq_ignore_ascii_case(tag))
+}
+
+/// Parses a response from the XML API, an HTML page instead of XML (like the one shown while Mod
+/// Archive is down for maintenance) gives you an [`Error::ServiceUnavailable`] rather than a
+/// confusing parse error.
+fn parse_xml(body: &str) -> Result<roxmltree::Document<'_>, Error> {
+    if looks_like_html(body.as_bytes()) {
+        return Err(Error::ServiceUnavailable);
+    }
+
+    match roxmltree::Document::parse(body) {
+        Ok(xml) => Ok(xml),
+        Err(e) => Err(Error::XMLParsingError(e)),
+    }
+}
+
+/// Picks the search result that's most likely the module you meant, the first one with exactly
+/// that filename (ignoring case) or otherwise just the first one
+fn best_match<'a>(results: &'a [ModSearchResolve], filename: &str) -> Option<&'a ModSearchResolve> {
+    results
+        .iter()
+        .find(|result| result.filename.eq_ignore_ascii_case(filename))
+        .or(results.first())
+}
+
+/// Only keeps the search results with the given filename
+fn exact_matches(
+    results: Vec<ModSearchResolve>,
+    filename: &str,
+    ignore_case: bool
+) -> Vec<ModSearchResolve> {
+    let filename = filename.trim();
+    results
+        .into_iter()
+        .filter(|result| {
+            if ignore_case {
+                result.filename.eq_ignore_ascii_case(filename)
+            } else {
+                result.filename == filename
+            }
+        })
+        .collect()
+}
+
+/// Replaces the value of every `key=` parameter in the text with `***`, so the API key doesn't
+/// end up in logs or error messages
+fn redact_key(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find("key=") {
+        let (before, after) = rest.split_at(at + "key=".len());
+        redacted.push_str(before);
+
+        // only the actual parameter, not something like monkey=. It isn't always right after a
+        // ? or &, ureq's Debug output has the query on its own in quotes
+        let is_parameter = !before[..at].ends_with(|c: char| c.is_ascii_alphanumeric());
+        let end = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~' | '%')))
+            .unwrap_or(after.len());
+        if is_parameter && end > 0 {
+            redacted.push_str("***");
+            rest = &after[end..];
+        } else {
+            rest = after;
+        }
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+/// Turns down API keys that can't possibly work before a request is wasted on them, keys are
+/// never empty and never have whitespace in them
+fn check_api_key(api_key: &str) -> Result<(), Error> {
+    if api_key.is_empty() || api_key.chars().any(char::is_whitespace) {
+        return Err(Error::InvalidApiKey);
+    }
+
+    Ok(())
+}
+
 /// (a helper function to make the code more readable, do not use directly)
 fn find_node_text(descendants: &[roxmltree::Node], tag: &str) -> Option<String> {
     descendants
@@ -55,28 +417,305 @@ fn find_node_text(descendants: &[roxmltree::Node], tag: &str) -> Option<String>
         .find_map(|node| node.text().map(|s| s.to_string()))
 }
 
+/// (a helper function to make the code more readable, do not use directly)
+fn find_title(descendants: &[roxmltree::Node]) -> Option<String> {
+    // the license has a title aswell, so the song title has to be looked for first
+    find_node_text(descendants, "songtitle").or_else(|| find_node_text(descendants, "title"))
+}
+
+/// (a helper function to make the code more readable, do not use directly)
+fn find_node_descendants<'a, 'input>(
+    descendants: &[roxmltree::Node<'a, 'input>],
+    tag: &str
+) -> Vec<roxmltree::Node<'a, 'input>> {
+    descendants
+        .iter()
+        .find(|node| node.has_tag_name(tag))
+        .map(|node| node.descendants().collect())
+        .unwrap_or_default()
+}
+
+/// Turns the text of an `<error>` node from the XML API into an [`Error`], running out of
+/// requests and a bad API key get their own errors since they say nothing about whether the
+/// module exists, anything we don't recognize ends up as [`Error::Unknown`]
+fn api_error(text: &str) -> Error {
+    let text = text.to_lowercase();
+    if text.contains("limit") || text.contains("exceeded") {
+        return Error::RateLimited;
+    }
+    if text.contains("key") {
+        return Error::InvalidApiKey;
+    }
+    if text.contains("not found") || text.contains("no module") || text.contains("does not exist") {
+        return Error::NotFound;
+    }
+
+    Error::Unknown
+}
+
 /// Error enum for functions in the crate that return a [`Result`]
-#[derive(Error, Debug)]
+#[derive(Error)]
 pub enum Error {
     #[error("The module was not found in Mod Archive")]
     NotFound,
-    #[error("There was a problem handling the API request: {0}")] APIRequestError(
-        #[from] Box<ureq::Error>,
+    #[error("The API key is empty, malformed or was turned down by Mod Archive")]
+    InvalidApiKey,
+    #[error("The API key has run out of requests for now")]
+    RateLimited,
+    /// The request URL (API key and all) is in the ureq error, so it isn't handed out as the
+    /// [`source()`](std::error::Error::source) of this one, print this error instead of it
+    #[error("There was a problem handling the API request: {}", redact_key(&.0.to_string()))]
+    APIRequestError(Box<ureq::Error>),
+    /// Same as [`Error::APIRequestError`], the reqwest error isn't the source since it has the
+    /// API key in it
+    #[cfg(feature = "async")]
+    #[error("There was a problem handling the async API request: {}", redact_key(&.0.to_string()))]
+    AsyncRequestError(reqwest::Error),
+    #[cfg(feature = "serde")]
+    #[error("There was a problem (de)serializing the JSON: {0}")] SerializationError(
+        #[from] serde_json::Error,
     ),
     #[error("There was a problem parsing the XML: {0}")] XMLParsingError(#[from] roxmltree::Error),
     #[error("There was an IO error: {0}")] IOError(#[from] std::io::Error),
+    #[error("There was a problem parsing the HTML: {0}")] HTMLParsingError(
+        #[from] tl::ParseError,
+    ),
+    #[error("The downloaded module's MD5 hash was {actual}, expected {expected}")] ChecksumMismatch {
+        expected: String,
+        actual: String,
+    },
+    #[error("The module is bigger than the limit of {0} bytes")] SizeLimitExceeded(u64),
+    #[error("{0:?} is not an MD5 hash, it has to be 32 hexadecimal characters")] InvalidHash(
+        String,
+    ),
+    #[error("Mod Archive sent back a response without a module in it")]
+    MalformedResponse,
+    #[error("Mod Archive is unavailable right now (most likely down for maintenance)")]
+    ServiceUnavailable,
+    #[error("Couldn't scrape the Mod Archive website, it might have changed: {0}")] ScrapeError(
+        String,
+    ),
     #[error("An unknown error occurred")]
     Unknown,
 }
 
-/// Simple struct to represent a search result, id and filename will be provided in each
-#[derive(Debug)]
+impl std::fmt::Debug for Error {
+    /// Identical to what deriving [`Debug`] would give you, except the API key is redacted from
+    /// the request URLs
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound => write!(f, "NotFound"),
+            Error::InvalidApiKey => write!(f, "InvalidApiKey"),
+            Error::RateLimited => write!(f, "RateLimited"),
+            Error::APIRequestError(e) =>
+                f
+                    .debug_tuple("APIRequestError")
+                    .field(&format_args!("{}", redact_key(&format!("{:?}", e))))
+                    .finish(),
+            #[cfg(feature = "async")]
+            Error::AsyncRequestError(e) =>
+                f
+                    .debug_tuple("AsyncRequestError")
+                    .field(&format_args!("{}", redact_key(&format!("{:?}", e))))
+                    .finish(),
+            #[cfg(feature = "serde")]
+            Error::SerializationError(e) => f.debug_tuple("SerializationError").field(e).finish(),
+            Error::XMLParsingError(e) => f.debug_tuple("XMLParsingError").field(e).finish(),
+            Error::IOError(e) => f.debug_tuple("IOError").field(e).finish(),
+            Error::HTMLParsingError(e) => f.debug_tuple("HTMLParsingError").field(e).finish(),
+            Error::ChecksumMismatch { expected, actual } =>
+                f
+                    .debug_struct("ChecksumMismatch")
+                    .field("expected", expected)
+                    .field("actual", actual)
+                    .finish(),
+            Error::SizeLimitExceeded(limit) => f.debug_tuple("SizeLimitExceeded").field(limit).finish(),
+            Error::InvalidHash(md5) => f.debug_tuple("InvalidHash").field(md5).finish(),
+            Error::MalformedResponse => write!(f, "MalformedResponse"),
+            Error::ServiceUnavailable => write!(f, "ServiceUnavailable"),
+            Error::ScrapeError(message) => f.debug_tuple("ScrapeError").field(message).finish(),
+            Error::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl Error {
+    /// Gives you the HTTP status code if the error came from the server answering with one (e.g.
+    /// `429` when rate limited or `500` when something broke on their end), errors which
+    /// happened before getting an answer like connection problems give you [`None`].
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            Error::APIRequestError(e) =>
+                match e.as_ref() {
+                    ureq::Error::Status(code, _) => Some(*code),
+                    ureq::Error::Transport(_) => None,
+                }
+            #[cfg(feature = "async")]
+            Error::AsyncRequestError(e) => e.status().map(|status| status.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Tells you what kind of error this is without holding onto what caused it, see
+    /// [`ErrorKind`].
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::NotFound => ErrorKind::NotFound,
+            Error::InvalidApiKey => ErrorKind::InvalidApiKey,
+            Error::RateLimited => ErrorKind::RateLimited,
+            Error::APIRequestError(_) => ErrorKind::Request,
+            #[cfg(feature = "async")]
+            Error::AsyncRequestError(_) => ErrorKind::Request,
+            #[cfg(feature = "serde")]
+            Error::SerializationError(_) => ErrorKind::Serialization,
+            Error::XMLParsingError(_) => ErrorKind::XMLParsing,
+            Error::IOError(_) => ErrorKind::IO,
+            Error::HTMLParsingError(_) => ErrorKind::HTMLParsing,
+            Error::ChecksumMismatch { .. } => ErrorKind::ChecksumMismatch,
+            Error::SizeLimitExceeded(_) => ErrorKind::SizeLimitExceeded,
+            Error::InvalidHash(_) => ErrorKind::InvalidHash,
+            Error::MalformedResponse => ErrorKind::MalformedResponse,
+            Error::ServiceUnavailable => ErrorKind::ServiceUnavailable,
+            Error::ScrapeError(_) => ErrorKind::Scrape,
+            Error::Unknown => ErrorKind::Unknown,
+        }
+    }
+
+    /// Gives you an owned copy of the error that can be cloned, stored and logged later (e.g.
+    /// collecting the failures of a batch of requests), see [`ErrorSummary`].
+    pub fn summary(&self) -> ErrorSummary {
+        ErrorSummary {
+            kind: self.kind(),
+            message: self.to_string(),
+            http_status: self.http_status(),
+        }
+    }
+}
+
+/// What kind of [`Error`](enum@Error) happened, one for each of its variants (both the blocking
+/// and async request errors are [`ErrorKind::Request`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorKind {
+    /// [`Error::NotFound`]
+    NotFound,
+    /// [`Error::InvalidApiKey`]
+    InvalidApiKey,
+    /// [`Error::RateLimited`]
+    RateLimited,
+    /// [`Error::APIRequestError`] (or `AsyncRequestError` with the `async` feature)
+    Request,
+    /// `SerializationError`, only ever made with the `serde` feature
+    Serialization,
+    /// [`Error::XMLParsingError`]
+    XMLParsing,
+    /// [`Error::IOError`]
+    IO,
+    /// [`Error::HTMLParsingError`]
+    HTMLParsing,
+    /// [`Error::ChecksumMismatch`]
+    ChecksumMismatch,
+    /// [`Error::SizeLimitExceeded`]
+    SizeLimitExceeded,
+    /// [`Error::InvalidHash`]
+    InvalidHash,
+    /// [`Error::MalformedResponse`]
+    MalformedResponse,
+    /// [`Error::ServiceUnavailable`]
+    ServiceUnavailable,
+    /// [`Error::ScrapeError`]
+    Scrape,
+    /// [`Error::Unknown`]
+    Unknown,
+}
+
+/// An owned snapshot of an [`Error`](enum@Error) made with [`Error::summary()`], unlike
+/// [`Error`](enum@Error) it can be cloned. The message is the same one it displays (so the API key
+/// is redacted from it)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorSummary {
+    /// What kind of error it was
+    pub kind: ErrorKind,
+    /// The error message
+    pub message: String,
+    /// The HTTP status code the server answered with, see [`Error::http_status()`]
+    pub http_status: Option<u16>,
+}
+
+impl std::fmt::Display for ErrorSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Simple struct to represent a search result, id, filename and title will be provided in each.
+///
+/// Two search results are the same module when they have the same ID, so comparing, hashing and
+/// ordering them (e.g. in a `HashSet` to dedupe across pages, or sorting) only looks at the ID.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModSearchResolve {
     pub id: u32,
     pub filename: String,
+    /// The title of the module as shown in the search results, can be empty for untitled ones
+    pub title: String,
+}
+
+/// What a search on the Mod Archive website looks at when matching your query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SearchType {
+    /// The filename of the module
+    Filename,
+    /// The title of the module
+    Title,
+    /// Either the filename or the title of the module
+    FilenameOrTitle,
+    /// The instrument text of the module
+    InstrumentText,
+}
+
+impl SearchType {
+    /// The name the website uses for the search type
+    fn as_str(&self) -> &'static str {
+        match self {
+            SearchType::Filename => "filename",
+            SearchType::Title => "songtitle",
+            SearchType::FilenameOrTitle => "filename_or_songtitle",
+            SearchType::InstrumentText => "instrument_text",
+        }
+    }
+}
+
+/// What to sort search results by, see [`ModSearchResolve::sort_results()`]. Without sorting
+/// the results stay in the order Mod Archive lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortKey {
+    /// The module ID, lowest first
+    Id,
+    /// The filename, alphabetically ignoring case
+    Filename,
+    /// The title, alphabetically ignoring case
+    Title,
+}
+
+/// A single page of search results, along with the total amount of pages the search has
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchResults {
+    /// The modules found on this page
+    pub items: Vec<ModSearchResolve>,
+    /// The total amount of modules found across every page, if Mod Archive didn't say then
+    /// this is only the amount on this page
+    pub total_results: u32,
+    /// The total amount of pages, `1` if everything fit on a single one
+    pub total_pages: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModSearch {
     pub searchtype: String,
     pub searchquery: String,
@@ -88,8 +727,10 @@ pub struct ModSearch {
     pub searchchannels: Option<String>,
 }
 
-/// Struct containing all of the info about a module
-#[derive(Debug)]
+/// Struct containing all of the info about a module. Comparing two of them looks at every field,
+/// wrap it in a [`ModInfoById`] to compare, hash and sort by the ID instead.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModInfo {
     /// The module ID of the module on Mod Archive
     pub id: u32,
@@ -97,9 +738,12 @@ pub struct ModInfo {
     pub filename: String,
     /// The title of the module
     pub title: String,
-    /// The file size of the module, use the
-    /// crate `byte-unit` to convert them to other units
+    /// The file size of the module as it's shown by Mod Archive, see
+    /// `size_bytes` if you need it as a number
     pub size: String,
+    /// The file size of the module in bytes, use the
+    /// crate `byte-unit` to convert them to other units
+    pub size_bytes: u64,
     /// The MD5 hash of the module file as a string
     pub md5: String,
     /// The format of the module, for example `XM`, `IT`
@@ -108,9 +752,11 @@ pub struct ModInfo {
     pub format: String,
     /// Spotlit module or not
     pub spotlit: bool,
-    /// Download count of the module at the time of scraping
+    /// Download count of the module at the time of scraping, `0` if Mod Archive didn't say (see
+    /// `raw_download_count` to tell those apart)
     pub download_count: u32,
-    /// Times the module has been favourited at the time of scraping
+    /// Times the module has been favourited at the time of scraping, `0` if Mod Archive didn't
+    /// say (see `raw_fav_count` to tell those apart)
     pub fav_count: u32,
     /// The time when it was scraped
     pub scrape_time: String,
@@ -122,211 +768,2104 @@ pub struct ModInfo {
     pub upload_date: String,
     /// The instrument text of the module
     pub instrument_text: String,
+    /// The average rating (out of 10) members gave the module in their comments, [`None`] if
+    /// nobody has rated it yet
+    pub average_rating: Option<f32>,
+    /// The amount of reviews the module has
+    pub review_count: u32,
+    /// The alias of the module's artist, [`None`] if there's no registered artist (common for
+    /// older modules)
+    pub artist: Option<String>,
+    /// The member ID of the module's artist on Mod Archive, [`None`] if there's no registered
+    /// artist
+    pub artist_id: Option<u32>,
+    /// The alias of the member who uploaded the module to Mod Archive when it isn't the artist,
+    /// [`None`] if Mod Archive doesn't say
+    pub uploader: Option<String>,
+    /// The title of the license the module is under (e.g. `Public Domain` or one of the Creative
+    /// Commons ones), [`None`] if Mod Archive doesn't list one
+    pub license: Option<String>,
+    /// The tags people gave the module on its page, these aren't in the XML so they're empty until
+    /// [`ModInfo::enrich_from_page()`] fills them in
+    pub tags: Vec<String>,
+    /// How many instruments the module has, [`None`] if Mod Archive doesn't say (formats like
+    /// MOD only have samples)
+    pub instrument_count: Option<u32>,
+    /// How many samples the module has, [`None`] if Mod Archive doesn't say
+    pub sample_count: Option<u32>,
+    /// How many patterns the module has, [`None`] if Mod Archive doesn't say
+    pub pattern_count: Option<u32>,
+    /// How many entries the module's order list has (a.k.a how many patterns get played through),
+    /// [`None`] if Mod Archive doesn't say
+    pub order_count: Option<u32>,
+    /// Identical to `download_count`, but [`None`] if Mod Archive didn't send one instead of `0`
+    pub raw_download_count: Option<u32>,
+    /// Identical to `fav_count`, but [`None`] if Mod Archive didn't send one instead of `0`
+    pub raw_fav_count: Option<u32>,
 }
 
-impl ModInfo {
-    /// (a helper function to make the code more readable, do not use directly)
-    fn _inner_request(mod_id: u32, api_key: &str) -> Result<String, crate::Error> {
-        let body = ureq
-            ::get(
-                format!("{BASEURL}?key={api_key}&request=view_by_moduleid&query={mod_id}").as_str()
-            )
-            .timeout(std::time::Duration::from_secs(60))
-            .call();
-
-        match body {
-            Ok(body) => Ok(body.into_string().unwrap_or_default()),
-            Err(e) => Err(crate::Error::APIRequestError(Box::new(e))),
-        }
-    }
-
-    /// Probably the singular most important function in this crate, takes a module ID (can be
-    /// generated at random, deliberately entered or acquired by resolving a filename and
-    /// picking a search result), and then gives you a full [`ModInfo`] struct.
-    pub fn get(mod_id: u32, api_key: &str) -> Result<ModInfo, crate::Error> {
-        let body = match Self::_inner_request(mod_id, api_key) {
-            Ok(body) => Some(body),
-            Err(e) => {
-                return Err(e);
-            }
-        };
+/// The popularity numbers of a module bundled together, see [`ModInfo::stats()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModStats {
+    /// How many times the module has been downloaded
+    pub downloads: u32,
+    /// How many people have the module in their favourites
+    pub favourites: u32,
+    /// The average rating out of 10, [`None`] if nobody has rated it yet
+    pub rating: Option<f32>,
+}
 
-        let body = body.unwrap();
+/// A comment someone left on a module, see [`ModInfo::get_reviews()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Review {
+    /// The alias of whoever wrote it
+    pub author: String,
+    /// The rating they gave out of 10, [`None`] if they didn't give one
+    pub rating: Option<u8>,
+    /// What they wrote
+    pub text: String,
+}
 
-        let id = mod_id;
-        let scrape_time = iso8601_time(&std::time::SystemTime::now());
+/// How many requests have been made with an API key, and how many it's allowed to make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestQuota {
+    /// The amount of requests made so far
+    pub current: u32,
+    /// The most requests the key can make
+    pub maximum: u32,
+}
 
-        let xml = match roxmltree::Document::parse(&body) {
-            Ok(xml) => xml,
-            Err(e) => {
-                return Err(crate::Error::XMLParsingError(e));
-            }
-        };
+/// A reusable client which holds onto your API key, construct it once with [`Client::new()`]
+/// and use it for all of your requests instead of passing the key to every single call. It asks
+/// for gzip compressed responses and unpacks them for you, which adds up on big search pages.
+/// Only gzip is asked for since `ureq` can't unpack deflate, the
+/// `AsyncClient` from the `async` feature asks for both.
+#[derive(Debug, Clone)]
+pub struct Client {
+    api_key: String,
+    agent: ureq::Agent,
+    base_url: String,
+    download_url: String,
+    timeout: std::time::Duration,
+    retries: u32,
+    quota: Option<std::sync::Arc<quota::QuotaState>>,
+}
 
-        let xml_descendants: Vec<_> = xml.descendants().collect();
+impl Default for Client {
+    /// A client without an API key, only good for the things which don't need one like
+    /// resolving filenames and downloading modules.
+    fn default() -> Client {
+        Client::new(String::new())
+    }
+}
 
-        if find_node_text(&xml_descendants, "error").is_some() {
-            return Err(crate::Error::NotFound);
+impl Client {
+    /// Creates a new client which will use the given API key for every request made with it.
+    pub fn new(api_key: impl Into<String>) -> Client {
+        Client {
+            api_key: api_key.into(),
+            agent: shared_agent(),
+            base_url: BASEURL.to_string(),
+            download_url: DOWNLOADURL.to_string(),
+            timeout: std::time::Duration::from_secs(60),
+            retries: 0,
+            quota: None,
         }
+    }
 
-        let filename = find_node_text(&xml_descendants, "filename").unwrap_or_default();
-        let title = find_node_text(&xml_descendants, "title").unwrap_or_default();
-        let size = find_node_text(&xml_descendants, "size").unwrap_or_default();
-        let md5 = find_node_text(&xml_descendants, "hash").unwrap_or_default();
-        let format = find_node_text(&xml_descendants, "format").unwrap_or_default();
-        let spotlit = false; // TODO: implement this
-        let download_count = find_node_text(&xml_descendants, "hits").unwrap_or_default();
-        let fav_count = find_node_text(&xml_descendants, "favoured").unwrap_or_default();
-        let channel_count = find_node_text(&xml_descendants, "channels").unwrap_or_default();
-        let genre = find_node_text(&xml_descendants, "genretext").unwrap_or_default();
-        let upload_date = find_node_text(&xml_descendants, "date").unwrap_or_default();
-        let instrument_text = find_node_text(
-            &xml_descendants,
-            "instruments"
-        ).unwrap_or_default();
-
-        // Cast some of the values to their correct types in the struct
-        let download_count = download_count.parse::<u32>().unwrap_or_default();
-        let fav_count = fav_count.parse::<u32>().unwrap_or_default();
-        let channel_count = channel_count.parse::<u32>().unwrap_or_default();
+    /// Points the client at another XML API base URL instead of Mod Archive's, mainly useful for
+    /// testing against a mock server.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Client {
+        self.base_url = base_url.into();
+        self
+    }
 
-        Ok(ModInfo {
-            id,
-            filename,
-            title,
-            size,
-            md5,
-            format,
-            spotlit,
-            download_count,
-            fav_count,
-            scrape_time,
-            channel_count,
-            genre,
-            upload_date,
-            instrument_text,
-        })
+    /// Sends every request through your own [`ureq::Agent`] instead of the one shared by the whole
+    /// crate, for tuning the connection itself (forcing IPv4 with a custom resolver, a connect
+    /// timeout separate from [`Client::with_timeout()`], redirects, TLS, proxies and so on).
+    pub fn with_agent(mut self, agent: ureq::Agent) -> Client {
+        self.agent = agent;
+        self
     }
 
-    /// Returns a Mod Archive download link for the given module, you can get this struct by using
-    /// [`ModInfo::get()`], or search using [`ModInfo::resolve_filename()`], if you're using the
-    /// resolver function please consider using the [`ModSearchResolve::get_download_link()`] method
-    /// instead.
-    pub fn get_download_link(&self) -> String {
-        format!("https://api.modarchive.org/downloads.php?moduleid={}#{}", self.id, self.filename)
+    /// Sends every request through the given proxy instead of the one from the `HTTP_PROXY`,
+    /// `HTTPS_PROXY` or `ALL_PROXY` environment variables (which are used when this isn't set),
+    /// make one with [`ureq::Proxy::new()`] like `ureq::Proxy::new("http://proxy.example:8080")`.
+    /// The client gets its own agent set up like the shared one (same redirects and timeout), so
+    /// this replaces an agent from [`Client::with_agent()`], put the proxy on your own agent
+    /// instead if you need both.
+    pub fn with_proxy(mut self, proxy: ureq::Proxy) -> Client {
+        self.agent = agent_builder(self.timeout).proxy(proxy).build();
+        self
     }
 
-    /// Return the raw bytes of a module file into a vector of bytes.
-    pub fn download_module(&self) -> Result<Vec<u8>, crate::Error> {
-        let link = Self::get_download_link(self);
+    /// Downloads modules from somewhere else instead of `https://api.modarchive.org/downloads.php`,
+    /// like a mirror or a caching proxy. It gets the same `?moduleid=` query the default one does.
+    pub fn with_download_url(mut self, download_url: impl Into<String>) -> Client {
+        self.download_url = download_url.into();
+        self
+    }
 
-        let body = match ureq::get(&link).call() {
-            Ok(body) => body,
-            Err(e) => {
-                return Err(crate::Error::APIRequestError(Box::new(e)));
-            }
-        };
+    /// Gives you the download link of a module the way this client downloads it, which is the
+    /// same as [`ModInfo::get_download_link()`] unless [`Client::with_download_url()`] was used.
+    pub fn download_link(&self, id: u32, filename: &str) -> String {
+        download_link_on(&self.download_url, id, filename)
+    }
 
-        let mut vector_of_bytes = Vec::new();
+    /// Sets how long a request can take before giving up on it, this applies to every request
+    /// the client makes including downloads, the default is 60 seconds.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Client {
+        self.timeout = timeout;
+        self
+    }
 
-        let _ = body
-            .into_reader()
-            .take(64_000_000)
-            .read_to_end(&mut vector_of_bytes)
-            .with_context(|| "Failed to create the buffer".to_string());
+    /// Retries requests which failed because of the network or a server error (5xx) up to the
+    /// given amount of times, waiting twice as long after each attempt. A module not existing or
+    /// a response that can't be parsed is never retried, the default is no retries at all.
+    pub fn with_retries(mut self, retries: u32) -> Client {
+        self.retries = retries;
+        self
+    }
 
-        Ok(vector_of_bytes)
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _get(&self, url: &str) -> ureq::Request {
+        self.agent.get(url).timeout(self.timeout).set("User-Agent", USER_AGENT)
     }
 
-    /// Searches for your string on Mod Archive and returns the results on the first page (a.k.a
-    /// only up to the first 40) as a vector of [`ModSearchResolve`]
-    // TODO: refactor this entire function
-    pub fn resolve_filename(filename: &str) -> Result<Vec<ModSearchResolve>, crate::Error> {
-        let body: String = ureq
-            ::get(
-                format!("https://modarchive.org/index.php?request=search&query={}&submit=Find&search_type=filename", filename).as_str()
-            )
-            .call()
-            .unwrap()
-            .into_string()
-            .unwrap();
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _call(&self, url: &str) -> Result<ureq::Response, crate::Error> {
+        self._call_with(url, &[])
+    }
 
-        let dom = tl::parse(&body, tl::ParserOptions::default()).unwrap();
-        let parser = dom.parser();
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _call_with(&self, url: &str, headers: &[(&str, &str)]) -> Result<ureq::Response, crate::Error> {
+        let mut attempt = 0;
+        loop {
+            log::debug!("GET {}", redact_key(url));
+            let request = headers.iter().fold(self._get(url), |request, (name, value)| request.set(name, value));
+            match request.call() {
+                Err(ureq::Error::Status(code, _)) if code >= 500 && attempt < self.retries => {
+                    log::warn!("{} answered with {}, retrying", redact_key(url), code);
+                }
+                Err(ureq::Error::Transport(e)) if attempt < self.retries => {
+                    log::warn!("Couldn't reach {} ({}), retrying", redact_key(url), e.kind());
+                }
+                Ok(response) => {
+                    log::debug!("{} answered with {}", redact_key(url), response.status());
+                    return Ok(response);
+                }
+                Err(e) => {
+                    log::warn!("GET {} failed: {}", redact_key(url), redact_key(&e.to_string()));
+                    return Err(crate::Error::APIRequestError(Box::new(e)));
+                }
+            }
 
-        let status = dom.query_selector("h1.site-wide-page-head-title");
+            std::thread::sleep(RETRY_BACKOFF.saturating_mul(2u32.saturating_pow(attempt)));
+            attempt += 1;
+        }
+    }
 
-        match status {
-            Some(_) => {}
-            None => {
-                return Err(crate::Error::NotFound);
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _inner_request(&self, request: &str, params: &[(&str, &str)]) -> Result<String, crate::Error> {
+        match check_api_key(&self.api_key) {
+            Ok(()) => {}
+            Err(e) => {
+                return Err(e);
             }
         }
-        // from this point on we can unwrap after each query selector
-        // because our info will for sure be present.
 
-        let links: Vec<ModSearchResolve> = dom
-            .query_selector("a.standard-link[title]")
-            .unwrap()
-            .map(|nodehandle| {
-                let node = nodehandle.get(parser).unwrap();
+        match self._check_quota() {
+            Ok(()) => {}
+            Err(e) => {
+                return Err(e);
+            }
+        }
 
-                let id = match node.as_tag().unwrap().attributes().get("href") {
-                    Some(Some(a)) =>
-                        a.as_utf8_str().split("query=").nth(1).unwrap().parse().unwrap(),
-                    Some(None) => unreachable!(),
-                    None => unreachable!(),
-                };
+        let (base_url, api_key) = (&self.base_url, &self.api_key);
+        let mut url = format!("{base_url}?key={api_key}&request={request}");
+        for (name, value) in params {
+            url.push_str(&format!("&{name}={}", percent_encode(value)));
+        }
+
+        let body = match self._call(&url) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
 
-                let filename = node.inner_text(parser).into();
+        // a body that couldn't be read isn't an empty one, don't let it turn into a parse error
+        match body.into_string() {
+            Ok(body) => Ok(body),
+            Err(e) => Err(crate::Error::IOError(e)),
+        }
+    }
 
-                ModSearchResolve { id, filename }
-            })
-            .collect();
+    /// Fetches the full [`ModInfo`] of a module using its ID, see [`ModInfo::get()`].
+    pub fn get(&self, mod_id: u32) -> Result<ModInfo, crate::Error> {
+        // module IDs start from 1, so there's no point in asking about 0
+        if mod_id == 0 {
+            return Err(crate::Error::NotFound);
+        }
+
+        let body = match self._inner_request("view_by_moduleid", &[("query", &mod_id.to_string())]) {
+            Ok(body) => Some(body),
+            Err(e) => {
+                return Err(e);
+            }
+        };
 
-        Ok(links)
+        let body = body.unwrap();
+
+        ModInfo::from_xml(&body, mod_id)
     }
 
-    pub fn track_requests(api_key: &str) -> Result<String, crate::Error> {
+    /// Fetches a module again and updates the numbers that change over time, see
+    /// [`ModInfo::refresh()`].
+    pub fn refresh(&self, modinfo: &mut ModInfo) -> Result<(), crate::Error> {
+        let fresh = match self.get(modinfo.id) {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        modinfo.download_count = fresh.download_count;
+        modinfo.fav_count = fresh.fav_count;
+        modinfo.raw_download_count = fresh.raw_download_count;
+        modinfo.raw_fav_count = fresh.raw_fav_count;
+        modinfo.average_rating = fresh.average_rating;
+        modinfo.review_count = fresh.review_count;
+        modinfo.scrape_time = fresh.scrape_time;
+
+        Ok(())
+    }
+
+    /// Fetches the unparsed XML of a module using its ID, see [`ModInfo::get_raw_xml()`].
+    pub fn get_raw_xml(&self, mod_id: u32) -> Result<String, crate::Error> {
+        // module IDs start from 1, so there's no point in asking about 0
+        if mod_id == 0 {
+            return Err(crate::Error::NotFound);
+        }
+
+        let body = match self._inner_request("view_by_moduleid", &[("query", &mod_id.to_string())]) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let xml = match parse_xml(&body) {
+            Ok(xml) => xml,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let xml_descendants: Vec<_> = xml.descendants().collect();
+
+        if let Some(error) = find_node_text(&xml_descendants, "error") {
+            return Err(api_error(&error));
+        }
+
+        Ok(body)
+    }
+
+    /// Fetches the comments people left on a module, see [`ModInfo::get_reviews()`].
+    pub fn get_reviews(&self, mod_id: u32) -> Result<Vec<Review>, crate::Error> {
+        // module IDs start from 1, so there's no point in asking about 0
+        if mod_id == 0 {
+            return Err(crate::Error::NotFound);
+        }
+
+        // com=1 has Mod Archive put the comments in with the rest of the module info
         let body = match
-            ureq
-                ::get(format!("{BASEURL}?key={api_key}&request=view_requests").as_str())
-                .timeout(std::time::Duration::from_secs(60))
-                .call()
+            self._inner_request("view_by_moduleid", &[("query", &mod_id.to_string()), ("com", "1")])
         {
             Ok(body) => body,
             Err(e) => {
-                return Err(crate::Error::APIRequestError(Box::new(e)));
+                return Err(e);
             }
         };
 
-        let body = match body.into_string() {
+        Review::_parse(&body)
+    }
+
+    /// Identical to [`Client::get()`], but a module that doesn't exist gives back `Ok(None)`, see
+    /// [`ModInfo::try_get()`].
+    pub fn try_get(&self, mod_id: u32) -> Result<Option<ModInfo>, crate::Error> {
+        match self.get(mod_id) {
+            Ok(modinfo) => Ok(Some(modinfo)),
+            Err(crate::Error::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Tells you if a module with the given ID exists, see [`ModInfo::exists()`].
+    pub fn exists(&self, mod_id: u32) -> Result<bool, crate::Error> {
+        match self.try_get(mod_id) {
+            Ok(modinfo) => Ok(modinfo.is_some()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fetches the full [`ModInfo`] of every module ID given, see [`ModInfo::get_many()`].
+    pub fn get_many(&self, ids: &[u32]) -> Vec<(u32, Result<ModInfo, crate::Error>)> {
+        ids.iter()
+            .map(|&mod_id| (mod_id, self.get(mod_id)))
+            .collect()
+    }
+
+    /// Fetches the full [`ModInfo`] of every module ID given, stopping at the first failure, see
+    /// [`ModInfo::get_list()`].
+    pub fn get_list(&self, ids: &[u32]) -> Result<Vec<ModInfo>, crate::Error> {
+        ids.iter()
+            .map(|&mod_id| self.get(mod_id))
+            .collect()
+    }
+
+    /// Lists the modules of an artist, see [`ModInfo::get_by_artist()`].
+    pub fn get_by_artist(&self, artist_id: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        match self.get_by_artist_page(artist_id, 1) {
+            Ok(results) => Ok(results.items),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Lists the modules of an artist on the given page, see [`ModInfo::get_by_artist_page()`].
+    pub fn get_by_artist_page(
+        &self,
+        artist_id: u32,
+        page: u32
+    ) -> Result<SearchResults, crate::Error> {
+        let body = match
+            self._inner_request(
+                "view_modules_by_artistid",
+                &[
+                    ("query", &artist_id.to_string()),
+                    ("page", &page.to_string()),
+                ]
+            )
+        {
             Ok(body) => body,
             Err(e) => {
-                return Err(crate::Error::IOError(e));
+                return Err(e);
+            }
+        };
+
+        match search::parse_module_list(&body) {
+            Ok(results) if results.items.is_empty() => Err(crate::Error::NotFound),
+            result => result,
+        }
+    }
+
+    /// Fetches the full [`ModInfo`] of the module with the given MD5 hash, see
+    /// [`ModInfo::get_by_hash()`].
+    pub fn get_by_hash(&self, md5: &str) -> Result<ModInfo, crate::Error> {
+        let md5 = md5.trim();
+        if md5.len() != 32 || !md5.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(crate::Error::InvalidHash(md5.to_string()));
+        }
+
+        let body = match
+            self._inner_request("search", &[
+                ("type", "hash"),
+                ("query", &md5.to_ascii_lowercase()),
+            ])
+        {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
             }
         };
 
-        let xml = match roxmltree::Document::parse(&body) {
+        let xml = match parse_xml(&body) {
             Ok(xml) => xml,
             Err(e) => {
-                return Err(crate::Error::XMLParsingError(e));
+                return Err(e);
             }
         };
 
         let xml_descendants: Vec<_> = xml.descendants().collect();
 
-        let current = find_node_text(&xml_descendants, "current").unwrap_or_default();
-        let maximum = find_node_text(&xml_descendants, "maximum").unwrap_or_default();
+        if let Some(error) = find_node_text(&xml_descendants, "error") {
+            return Err(api_error(&error));
+        }
+
+        // a hash nobody uploaded gives back an empty list instead of an error
+        if !xml_descendants.iter().any(|node| node.has_tag_name("module")) {
+            return Err(crate::Error::NotFound);
+        }
 
-        Ok(format!("{} requests made out of {}", current, maximum))
+        ModInfo::_parse_nodes(&xml_descendants, None)
     }
-}
 
-impl ModSearchResolve {
-    /// Get the download link of this specific module.
-    pub fn get_download_link(&self) -> String {
-        format!("https://api.modarchive.org/downloads.php?moduleid={}#{}", self.id, self.filename)
+    /// Fetches the full [`ModInfo`] of a randomly picked module, see [`ModInfo::get_random()`].
+    pub fn get_random(&self) -> Result<ModInfo, crate::Error> {
+        let body = match self._inner_request("random", &[]) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        ModInfo::_parse(&body, None)
+    }
+
+    /// Downloads the raw bytes of a module file, see [`ModInfo::download_module()`].
+    pub fn download_module(&self, modinfo: &ModInfo) -> Result<Vec<u8>, crate::Error> {
+        self.download_module_with_limit(modinfo, MAX_MODULE_SIZE)
+    }
+
+    /// Downloads the raw bytes of a module file with your own size limit, see
+    /// [`ModInfo::download_module_with_limit()`].
+    pub fn download_module_with_limit(
+        &self,
+        modinfo: &ModInfo,
+        max_bytes: u64
+    ) -> Result<Vec<u8>, crate::Error> {
+        self._download(&self.download_link(modinfo.id, &modinfo.filename), max_bytes, |_, _| {})
+    }
+
+    /// Downloads the raw bytes of a module file while telling you how far along it is, see
+    /// [`ModInfo::download_with_progress()`].
+    pub fn download_with_progress(
+        &self,
+        modinfo: &ModInfo,
+        progress: impl FnMut(u64, Option<u64>)
+    ) -> Result<Vec<u8>, crate::Error> {
+        self._download(&self.download_link(modinfo.id, &modinfo.filename), MAX_MODULE_SIZE, progress)
+    }
+
+    /// Downloads the raw bytes of a module file using only its ID and filename, see
+    /// [`ModInfo::download_by_id()`].
+    pub fn download_by_id(&self, id: u32, filename: &str) -> Result<Vec<u8>, crate::Error> {
+        self._download(&self.download_link(id, filename), MAX_MODULE_SIZE, |_, _| {})
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _download(
+        &self,
+        link: &str,
+        max_bytes: u64,
+        progress: impl FnMut(u64, Option<u64>)
+    ) -> Result<Vec<u8>, crate::Error> {
+        let body = match self._call(link) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let total = body.header("Content-Length").and_then(|length| length.parse::<u64>().ok());
+
+        let bytes = match read_module(body.into_reader(), max_bytes, total, progress) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        // an error page instead of the module, don't hand it back as if it were one
+        if looks_like_html(&bytes) {
+            return Err(crate::Error::MalformedResponse);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Downloads the raw bytes of a module file unless its MD5 hash is the one you already have,
+    /// see [`ModInfo::download_if_changed()`].
+    pub fn download_if_changed(
+        &self,
+        modinfo: &ModInfo,
+        known_md5: &str
+    ) -> Result<Option<Vec<u8>>, crate::Error> {
+        if modinfo.md5.trim().eq_ignore_ascii_case(known_md5.trim()) {
+            return Ok(None);
+        }
+
+        match self.download_module(modinfo) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Downloads the raw bytes of a module file and checks them against its MD5 hash, see
+    /// [`ModInfo::download_verified()`].
+    pub fn download_verified(&self, modinfo: &ModInfo) -> Result<Vec<u8>, crate::Error> {
+        let bytes = match self.download_module(modinfo) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let actual = ModInfo::md5_of(&bytes);
+        if !actual.eq_ignore_ascii_case(modinfo.md5.trim()) {
+            return Err(crate::Error::ChecksumMismatch {
+                expected: modinfo.md5.clone(),
+                actual,
+            });
+        }
+
+        Ok(bytes)
+    }
+
+    /// Streams a module file straight into a file at the given path, see
+    /// [`ModInfo::download_to()`].
+    pub fn download_to<P: AsRef<Path>>(&self, modinfo: &ModInfo, path: P) -> Result<u64, crate::Error> {
+        self._download_to(modinfo, path.as_ref(), MAX_MODULE_SIZE)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _download_to(&self, modinfo: &ModInfo, path: &Path, max_bytes: u64) -> Result<u64, crate::Error> {
+        let link = self.download_link(modinfo.id, &modinfo.filename);
+
+        let body = match self._call(&link) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        // peek at the start first so an error page doesn't end up saved as the module
+        let mut reader = std::io::BufReader::new(body.into_reader());
+        match reader.fill_buf() {
+            Ok(start) if looks_like_html(start) => {
+                return Err(crate::Error::MalformedResponse);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        }
+
+        let mut file = match std::fs::File::create(path) {
+            Ok(file) => file,
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        };
+
+        match copy_module(reader, &mut file, max_bytes) {
+            Ok(written) => Ok(written),
+            Err(e) => {
+                // don't leave the cut off module behind as if it were the whole thing
+                drop(file);
+                let _ = std::fs::remove_file(path);
+                Err(e)
+            }
+        }
+    }
+
+    /// Downloads the rest of a module file that was only partly downloaded into `existing`, see
+    /// [`ModInfo::download_resume()`].
+    pub fn download_resume(&self, modinfo: &ModInfo, existing: &mut std::fs::File) -> Result<(), crate::Error> {
+        self._download_resume(modinfo, existing, MAX_MODULE_SIZE)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _download_resume(
+        &self,
+        modinfo: &ModInfo,
+        existing: &mut std::fs::File,
+        max_bytes: u64
+    ) -> Result<(), crate::Error> {
+        let have = match existing.metadata() {
+            Ok(metadata) => metadata.len(),
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        };
+
+        if modinfo.size_bytes > 0 && have == modinfo.size_bytes {
+            return Ok(());
+        }
+
+        let link = self.download_link(modinfo.id, &modinfo.filename);
+        // more than the module should have means the file is something else, start over
+        let start = if modinfo.size_bytes > 0 && have > modinfo.size_bytes { 0 } else { have };
+
+        let body = match self._call_with(&link, &[("Range", &format!("bytes={start}-"))]) {
+            // the server doesn't think there's anything left to send, get all of it instead
+            Err(e) if e.http_status() == Some(416) => self._call(&link),
+            body => body,
+        };
+        let body = match body {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        // only a 206 is the rest of the file, anything else is all of it
+        let resumed = body.status() == 206;
+        let offset = if resumed { start } else { 0 };
+
+        let mut reader = std::io::BufReader::new(body.into_reader());
+        match reader.fill_buf() {
+            Ok(chunk) if looks_like_html(chunk) => {
+                return Err(crate::Error::MalformedResponse);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        }
+
+        match existing.set_len(offset).and_then(|()| existing.seek(std::io::SeekFrom::Start(offset))) {
+            Ok(_) => {}
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        }
+
+        // the limit is for the whole module, not only the part that's left
+        match copy_module(reader, existing, max_bytes.saturating_sub(offset)) {
+            Ok(_) => {}
+            Err(crate::Error::SizeLimitExceeded(_)) => {
+                // put the file back the way it was instead of leaving it cut off at the limit
+                let _ = existing.set_len(offset);
+                return Err(crate::Error::SizeLimitExceeded(max_bytes));
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        }
+
+        let length = match existing.metadata() {
+            Ok(metadata) => metadata.len(),
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        };
+        if modinfo.size_bytes > 0 && length != modinfo.size_bytes {
+            return Err(
+                crate::Error::IOError(
+                    std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        format!("the file is {length} bytes instead of {}", modinfo.size_bytes)
+                    )
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Downloads every module in the search results into a directory, see
+    /// [`ModInfo::download_search_results()`].
+    pub fn download_results<P: AsRef<Path>>(
+        &self,
+        results: &[ModSearchResolve],
+        dir: P
+    ) -> Vec<Result<PathBuf, crate::Error>> {
+        self._download_results(results, dir.as_ref(), MAX_MODULE_SIZE)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _download_results(
+        &self,
+        results: &[ModSearchResolve],
+        dir: &Path,
+        max_bytes: u64
+    ) -> Vec<Result<PathBuf, crate::Error>> {
+        results
+            .iter()
+            .map(|result| {
+                let modinfo = match self.get(result.id) {
+                    Ok(modinfo) => modinfo,
+                    Err(e) => {
+                        return Err(e);
+                    }
+                };
+
+                // the ID goes first since different versions can have the same filename, and
+                // only the last part of the filename is used so it can't point outside of dir
+                let filename = Path::new(&modinfo.filename)
+                    .file_name()
+                    .map(|filename| filename.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let path = dir.join(format!("{}_{}", modinfo.id, filename));
+
+                // already downloaded, no point in doing it again
+                if let Ok(existing) = std::fs::read(&path) {
+                    let same_size = (existing.len() as u64) == modinfo.size_bytes;
+                    if same_size && ModInfo::md5_of(&existing).eq_ignore_ascii_case(modinfo.md5.trim()) {
+                        return Ok(path);
+                    }
+                }
+
+                // a module over the limit gives its error here and isn't left behind cut off
+                match self._download_to(&modinfo, &path, max_bytes) {
+                    Ok(_) => Ok(path),
+                    Err(e) => Err(e),
+                }
+            })
+            .collect()
+    }
+
+    /// Resolves a filename and downloads every module the search finds into a directory, see
+    /// [`ModInfo::download_search_results()`].
+    pub fn download_search_results<P: AsRef<Path>>(
+        &self,
+        filename: &str,
+        dir: P
+    ) -> Result<Vec<Result<PathBuf, crate::Error>>, crate::Error> {
+        match self.resolve_filename(filename) {
+            Ok(results) => Ok(self.download_results(&results, dir)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Searches for your string on Mod Archive, see [`ModInfo::resolve_filename()`].
+    pub fn resolve_filename(&self, filename: &str) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        match self.resolve_filename_page(filename, 1) {
+            Ok(results) => Ok(results.items),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Resolves a filename and fetches the full info of the best match, see
+    /// [`ModInfo::resolve_one()`].
+    pub fn resolve_one(&self, filename: &str) -> Result<ModInfo, crate::Error> {
+        let results = match self.resolve_filename(filename) {
+            Ok(results) => results,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        match best_match(&results, filename) {
+            Some(result) => self.get(result.id),
+            None => Err(crate::Error::NotFound),
+        }
+    }
+
+    /// Searches for your string on Mod Archive and only keeps the results with that exact
+    /// filename, see [`ModInfo::resolve_filename_exact()`].
+    pub fn resolve_filename_exact(
+        &self,
+        filename: &str,
+        ignore_case: bool
+    ) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        match self.resolve_filename(filename) {
+            Ok(results) => Ok(exact_matches(results, filename, ignore_case)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Searches for your string on Mod Archive and only keeps the results with the given file
+    /// extension, see [`ModInfo::resolve_filename_by_format()`].
+    pub fn resolve_filename_by_format(
+        &self,
+        filename: &str,
+        format: Format
+    ) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        match self.resolve_filename(filename) {
+            Ok(results) => {
+                Ok(
+                    results
+                        .into_iter()
+                        .filter(|result| result.extension_format() == format)
+                        .collect()
+                )
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Searches for your string on Mod Archive and gives you the results on the given page, see
+    /// [`ModInfo::resolve_filename_page()`].
+    pub fn resolve_filename_page(
+        &self,
+        filename: &str,
+        page: u32
+    ) -> Result<SearchResults, crate::Error> {
+        self.resolve_page(filename, SearchType::Filename, page)
+    }
+
+    /// Searches for your string on Mod Archive by the given [`SearchType`], see
+    /// [`ModInfo::resolve()`].
+    pub fn resolve(
+        &self,
+        query: &str,
+        search_type: SearchType
+    ) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        match self.resolve_page(query, search_type, 1) {
+            Ok(results) => Ok(results.items),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Searches for your string on Mod Archive by the given [`SearchType`] and gives you the
+    /// results on the given page, see [`ModInfo::resolve_page()`].
+    pub fn resolve_page(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        page: u32
+    ) -> Result<SearchResults, crate::Error> {
+        let body = match self._call(&search_page_url(query, search_type, page)) {
+            Ok(body) => body,
+            Err(e) if e.http_status() == Some(404) => {
+                return Err(crate::Error::NotFound);
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let body = match read_page(body.into_reader(), MAX_PAGE_SIZE) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        ModInfo::_parse_search_page(&body, page)
+    }
+
+    /// Fills in the parts of a module only its web page has, see [`ModInfo::enrich_from_page()`].
+    pub fn enrich_from_page(&self, modinfo: &mut ModInfo) -> Result<(), crate::Error> {
+        let body = match self._call(&modinfo.module_page_url()) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let body = match read_page(body.into_reader(), MAX_PAGE_SIZE) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        match ModInfo::_parse_module_page(&body) {
+            Ok(tags) => {
+                modinfo.tags = tags;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Scrapes what it can about a module off its web page without using the API key, see
+    /// [`ModInfo::get_scraped()`].
+    pub fn get_scraped(&self, mod_id: u32) -> Result<ModInfo, crate::Error> {
+        // module IDs start from 1, so there's no point in asking about 0
+        if mod_id == 0 {
+            return Err(crate::Error::NotFound);
+        }
+
+        let body = match self._call(&module_page_link(mod_id)) {
+            Ok(body) => body,
+            Err(e) if e.http_status() == Some(404) => {
+                return Err(crate::Error::NotFound);
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let body = match read_page(body.into_reader(), MAX_PAGE_SIZE) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        ModInfo::_parse_scraped_page(&body, mod_id)
+    }
+
+    /// Tells you how many requests have been made with this client's API key so far, see
+    /// [`ModInfo::request_quota()`].
+    pub fn request_quota(&self) -> Result<RequestQuota, crate::Error> {
+        match check_api_key(&self.api_key) {
+            Ok(()) => {}
+            Err(e) => {
+                return Err(e);
+            }
+        }
+
+        let (base_url, api_key) = (&self.base_url, &self.api_key);
+        let body = match
+            self._call(format!("{base_url}?key={api_key}&request=view_requests").as_str())
+        {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let body = match body.into_string() {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        };
+
+        RequestQuota::_parse(&body)
+    }
+
+    /// Tells you how many requests have been made with this client's API key so far, see
+    /// [`ModInfo::track_requests()`].
+    pub fn track_requests(&self) -> Result<String, crate::Error> {
+        match self.request_quota() {
+            Ok(quota) => Ok(format!("{} requests made out of {}", quota.current, quota.maximum)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl RequestQuota {
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _parse(body: &str) -> Result<RequestQuota, crate::Error> {
+        let xml = match parse_xml(body) {
+            Ok(xml) => xml,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let xml_descendants: Vec<_> = xml.descendants().collect();
+
+        if let Some(error) = find_node_text(&xml_descendants, "error") {
+            return Err(api_error(&error));
+        }
+
+        // a missing count isn't 0, that would look like a key with no requests left
+        let count = |tag: &str| {
+            find_node_text(&xml_descendants, tag).and_then(|count| count.trim().parse::<u32>().ok())
+        };
+
+        match (count("current"), count("maximum")) {
+            (Some(current), Some(maximum)) => Ok(RequestQuota { current, maximum }),
+            _ => Err(crate::Error::MalformedResponse),
+        }
+    }
+}
+
+impl Review {
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _parse(body: &str) -> Result<Vec<Review>, crate::Error> {
+        let xml = match parse_xml(body) {
+            Ok(xml) => xml,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let xml_descendants: Vec<_> = xml.descendants().collect();
+
+        if let Some(error) = find_node_text(&xml_descendants, "error") {
+            return Err(api_error(&error));
+        }
+
+        if !xml_descendants.iter().any(|node| node.has_tag_name("module")) {
+            return Err(crate::Error::MalformedResponse);
+        }
+
+        // the module has a comment node of its own, so only the ones inside comments count
+        let reviews = xml_descendants
+            .iter()
+            .filter(|node| node.has_tag_name("comments"))
+            .flat_map(|node| node.children())
+            .filter(|node| node.has_tag_name("comment"))
+            .map(|node| {
+                let descendants: Vec<_> = node.descendants().collect();
+                let author = find_node_text(&descendants, "alias").unwrap_or_default();
+                // the scale goes from 0 up to 10, anything else isn't a rating
+                let rating = find_node_text(&descendants, "rating")
+                    .and_then(|rating| rating.trim().parse::<u8>().ok())
+                    .filter(|rating| *rating <= 10);
+                let text = find_node_text(&descendants, "text").unwrap_or_default();
+
+                Review { author, rating, text }
+            })
+            .collect();
+
+        Ok(reviews)
+    }
+}
+
+impl ModInfo {
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _parse(body: &str, mod_id: Option<u32>) -> Result<ModInfo, crate::Error> {
+        let xml = match parse_xml(body) {
+            Ok(xml) => xml,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        let xml_descendants: Vec<_> = xml.descendants().collect();
+        ModInfo::_parse_nodes(&xml_descendants, mod_id)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _parse_nodes(
+        xml_descendants: &[roxmltree::Node],
+        mod_id: Option<u32>
+    ) -> Result<ModInfo, crate::Error> {
+        let scrape_time = iso8601_time(&std::time::SystemTime::now());
+
+        if let Some(error) = find_node_text(xml_descendants, "error") {
+            return Err(api_error(&error));
+        }
+
+        // a cut off response can still be valid XML, but everything would end up empty
+        if !xml_descendants.iter().any(|node| node.has_tag_name("module")) {
+            return Err(crate::Error::MalformedResponse);
+        }
+
+        // modules fetched without knowing their ID beforehand (like random ones) get it from the
+        // response itself
+        let id = match mod_id {
+            Some(mod_id) => mod_id,
+            None =>
+                find_node_text(xml_descendants, "id")
+                    .and_then(|id| id.parse::<u32>().ok())
+                    .unwrap_or_default(),
+        };
+
+        let filename = find_node_text(xml_descendants, "filename").unwrap_or_default();
+        let title = find_title(xml_descendants).unwrap_or_default();
+        let size = find_node_text(xml_descendants, "size").unwrap_or_default();
+        let size_bytes = find_node_text(xml_descendants, "bytes").unwrap_or_else(|| size.clone());
+        let md5 = find_node_text(xml_descendants, "hash").unwrap_or_default();
+        let format = find_node_text(xml_descendants, "format").unwrap_or_default();
+        // spotlit modules have the state of the featured node filled in, otherwise it's empty
+        let featured_descendants = find_node_descendants(xml_descendants, "featured");
+        let spotlit = find_node_text(&featured_descendants, "state").is_some_and(
+            |state| !state.trim().is_empty()
+        );
+        let download_count = find_node_text(xml_descendants, "hits");
+        let fav_count = find_node_text(xml_descendants, "favoured");
+        let channel_count = find_node_text(xml_descendants, "channels").unwrap_or_default();
+        let genre = find_node_text(xml_descendants, "genretext").unwrap_or_default();
+        let upload_date = find_node_text(xml_descendants, "date").unwrap_or_default();
+        let instrument_text = find_node_text(
+            xml_descendants,
+            "instruments"
+        ).unwrap_or_default();
+
+        let ratings_descendants = find_node_descendants(xml_descendants, "overall_ratings");
+        let rating = find_node_text(&ratings_descendants, "comment_rating").unwrap_or_default();
+        let rating_count = find_node_text(&ratings_descendants, "comment_total").unwrap_or_default();
+        let review_count = find_node_text(&ratings_descendants, "review_total").unwrap_or_default();
+
+        // only the first artist is picked up, older modules might not have one at all. Whoever
+        // only uploaded the module says so in their description, they aren't the artist
+        let artist_info_descendants = find_node_descendants(xml_descendants, "artist_info");
+        let artists: Vec<(Vec<_>, bool)> = artist_info_descendants
+            .iter()
+            .filter(|node| node.has_tag_name("artist"))
+            .map(|node| {
+                let descendants: Vec<_> = node.descendants().collect();
+                let uploaded = find_node_text(&descendants, "module_description").is_some_and(
+                    |description| description.to_lowercase().contains("upload")
+                );
+                (descendants, uploaded)
+            })
+            .collect();
+        let artist_descendants = artists
+            .iter()
+            .find(|(_, uploaded)| !uploaded)
+            .map(|(descendants, _)| descendants.as_slice())
+            .unwrap_or_default();
+        let artist = find_node_text(artist_descendants, "alias");
+        let artist_id = find_node_text(artist_descendants, "id");
+        let uploader = artists
+            .iter()
+            .find(|(_, uploaded)| *uploaded)
+            .and_then(|(descendants, _)| find_node_text(descendants, "alias"));
+
+        let instrument_count = find_node_text(xml_descendants, "instrument_count");
+        let sample_count = find_node_text(xml_descendants, "sample_count");
+        let pattern_count = find_node_text(xml_descendants, "pattern_count");
+        let order_count = find_node_text(xml_descendants, "order_count");
+
+        let license_descendants = find_node_descendants(xml_descendants, "license");
+        let license = find_node_text(&license_descendants, "title").filter(
+            |license| !license.trim().is_empty()
+        );
+
+        // Cast some of the values to their correct types in the struct
+        let raw_download_count = download_count.and_then(|count| count.trim().parse::<u32>().ok());
+        let raw_fav_count = fav_count.and_then(|count| count.trim().parse::<u32>().ok());
+        let download_count = raw_download_count.unwrap_or_default();
+        let fav_count = raw_fav_count.unwrap_or_default();
+        let channel_count = channel_count.parse::<u32>().unwrap_or_default();
+        let size_bytes = size_bytes.parse::<u64>().unwrap_or_default();
+        let review_count = review_count.parse::<u32>().unwrap_or_default();
+        let artist_id = artist_id.and_then(|id| id.parse::<u32>().ok());
+        let instrument_count = instrument_count.and_then(|count| count.trim().parse::<u32>().ok());
+        let sample_count = sample_count.and_then(|count| count.trim().parse::<u32>().ok());
+        let pattern_count = pattern_count.and_then(|count| count.trim().parse::<u32>().ok());
+        let order_count = order_count.and_then(|count| count.trim().parse::<u32>().ok());
+        let average_rating = match rating_count.parse::<u32>() {
+            Ok(count) if count > 0 => rating.parse::<f32>().ok(),
+            _ => None,
+        };
+
+        Ok(ModInfo {
+            id,
+            filename,
+            title,
+            size,
+            size_bytes,
+            md5,
+            format,
+            spotlit,
+            download_count,
+            fav_count,
+            scrape_time,
+            channel_count,
+            genre,
+            upload_date,
+            instrument_text,
+            average_rating,
+            review_count,
+            artist,
+            artist_id,
+            uploader,
+            license,
+            tags: Vec::new(),
+            instrument_count,
+            sample_count,
+            pattern_count,
+            order_count,
+            raw_download_count,
+            raw_fav_count,
+        })
+    }
+
+    /// Parses the XML Mod Archive gives back when viewing a module into a [`ModInfo`] struct,
+    /// this is what [`ModInfo::get()`] uses after fetching it. Handy if you got the response by
+    /// some other means or want to test against a saved one.
+    pub fn from_xml(xml: &str, mod_id: u32) -> Result<ModInfo, crate::Error> {
+        Self::_parse(xml, Some(mod_id))
+    }
+
+    /// Probably the singular most important function in this crate, takes a module ID (can be
+    /// generated at random, deliberately entered or acquired by resolving a filename and
+    /// picking a search result), and then gives you a full [`ModInfo`] struct. Module IDs start
+    /// from 1, so an ID of 0 gives you [`Error::NotFound`] without making a request.
+    ///
+    /// If you're going to make a lot of requests, consider creating a [`Client`] once and
+    /// using [`Client::get()`] instead.
+    pub fn get(mod_id: u32, api_key: &str) -> Result<ModInfo, crate::Error> {
+        Client::new(api_key).get(mod_id)
+    }
+
+    /// Fetches the module again by its ID and updates the download count, favourite count,
+    /// rating and review count in place, along with the `scrape_time`. Everything else is left
+    /// alone. A module that got removed in the meantime gives you [`Error::NotFound`] like
+    /// [`ModInfo::get()`] would, and leaves the struct untouched.
+    pub fn refresh(&mut self, api_key: &str) -> Result<(), crate::Error> {
+        Client::new(api_key).refresh(self)
+    }
+
+    /// Gives you the XML Mod Archive sent back for a module as-is, for getting at the parts the
+    /// [`ModInfo`] struct doesn't have (yet). An unknown module still gives you
+    /// [`Error::NotFound`], and the XML can be turned into a [`ModInfo`] later with
+    /// [`ModInfo::from_xml()`].
+    pub fn get_raw_xml(mod_id: u32, api_key: &str) -> Result<String, crate::Error> {
+        Client::new(api_key).get_raw_xml(mod_id)
+    }
+
+    /// Fetches the comments people left on the module, each with who wrote it and the rating
+    /// they gave out of 10 (if they gave one). A module nobody commented on gives you an empty
+    /// vector.
+    pub fn get_reviews(&self, api_key: &str) -> Result<Vec<Review>, crate::Error> {
+        Client::new(api_key).get_reviews(self.id)
+    }
+
+    /// Identical to [`ModInfo::get()`], except a module which doesn't exist gives you `Ok(None)`
+    /// instead of [`Error::NotFound`], network and parsing problems are still returned as errors.
+    /// Handy when probing a bunch of IDs where plenty of them are expected to be missing.
+    pub fn try_get(mod_id: u32, api_key: &str) -> Result<Option<ModInfo>, crate::Error> {
+        Client::new(api_key).try_get(mod_id)
+    }
+
+    /// Tells you if a module with the given ID exists on Mod Archive, a module that doesn't gives
+    /// you `Ok(false)` while network and parsing problems are still returned as errors. This
+    /// still fetches the whole module, so it costs a request like [`ModInfo::get()`] does.
+    pub fn exists(mod_id: u32, api_key: &str) -> Result<bool, crate::Error> {
+        Client::new(api_key).exists(mod_id)
+    }
+
+    /// Fetches the full [`ModInfo`] of every module ID given over the same connection, each ID
+    /// gets its own result so a single failure doesn't stop the rest from being fetched. The
+    /// results are in the same order the IDs were given in.
+    pub fn get_many(ids: &[u32], api_key: &str) -> Vec<(u32, Result<ModInfo, crate::Error>)> {
+        Client::new(api_key).get_many(ids)
+    }
+
+    /// Identical to [`ModInfo::get_many()`], but gives you just the [`ModInfo`] structs and stops
+    /// at the first module that fails. The XML API has no way of asking for several modules in
+    /// one request, so each ID still costs a request against your quota.
+    pub fn get_list(ids: &[u32], api_key: &str) -> Result<Vec<ModInfo>, crate::Error> {
+        Client::new(api_key).get_list(ids)
+    }
+
+    /// Lists the modules made by the artist with the given member ID (the first page of them),
+    /// an artist without any modules gives you [`Error::NotFound`].
+    pub fn get_by_artist(artist_id: u32, api_key: &str) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::new(api_key).get_by_artist(artist_id)
+    }
+
+    /// Identical to [`ModInfo::get_by_artist()`], but gives you the modules on the given page
+    /// (starting from 1) along with the total amount of pages.
+    pub fn get_by_artist_page(
+        artist_id: u32,
+        page: u32,
+        api_key: &str
+    ) -> Result<SearchResults, crate::Error> {
+        Client::new(api_key).get_by_artist_page(artist_id, page)
+    }
+
+    /// Lists the modules in the genre with the given ID on the given page (starting from 1), the
+    /// valid genre IDs can be found in [`GENRES`].
+    pub fn search_by_genre(
+        genre_id: u32,
+        page: u32,
+        api_key: &str
+    ) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::new(api_key).search_by_genre(genre_id, page)
+    }
+
+    /// Lists the most downloaded modules of all time on the given page (starting from 1), most
+    /// downloaded first. The XML API doesn't have charts, so this is scraped off the chart page
+    /// of the website like [`ModInfo::latest_additions()`] and doesn't need an API key.
+    pub fn top_downloads(page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::default().top_downloads(page)
+    }
+
+    /// Lists the most favourited modules of all time on the given page (starting from 1), most
+    /// favourited first, scraped off the website the same way as [`ModInfo::top_downloads()`].
+    pub fn top_favourites(page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::default().top_favourites(page)
+    }
+
+    /// Lists the modules most recently uploaded to Mod Archive on the given page (starting from
+    /// 1), newest first, handy for a "what's new" section. The XML API doesn't have a listing for
+    /// this, so it's scraped off the latest additions page of the website the same way
+    /// [`ModInfo::resolve_filename()`] scrapes the search page, which means it doesn't need an
+    /// API key.
+    pub fn latest_additions(page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::default().latest_additions(page)
+    }
+
+    /// Looks up a module by the MD5 hash of its file and gives you its full [`ModInfo`] struct,
+    /// handy for finding out whether a file you already have is on Mod Archive and what it is.
+    /// A hash which isn't 32 hexadecimal characters gives you [`Error::InvalidHash`] without
+    /// making a request, and one that doesn't match anything gives you [`Error::NotFound`].
+    pub fn get_by_hash(md5: &str, api_key: &str) -> Result<ModInfo, crate::Error> {
+        Client::new(api_key).get_by_hash(md5)
+    }
+
+    /// Gives you the full [`ModInfo`] struct of a randomly picked module on Mod Archive, handy for
+    /// "module of the moment" kinds of things.
+    pub fn get_random(api_key: &str) -> Result<ModInfo, crate::Error> {
+        Client::new(api_key).get_random()
+    }
+
+    /// Returns the link to the module's page on the Mod Archive website, the one people would
+    /// look at in their browser (not to be confused with [`ModInfo::get_download_link()`]).
+    pub fn module_page_url(&self) -> String {
+        module_page_link(self.id)
+    }
+
+    /// Returns a Mod Archive download link for the given module, you can get this struct by using
+    /// [`ModInfo::get()`], or search using [`ModInfo::resolve_filename()`], if you're using the
+    /// resolver function please consider using the [`ModSearchResolve::get_download_link()`] method
+    /// instead.
+    pub fn get_download_link(&self) -> String {
+        download_link(self.id, &self.filename)
+    }
+
+    /// Gives you the instrument text with the whitespace around it and around each line trimmed
+    /// off, the line breaks between the lines are kept. The raw text stays in `instrument_text`.
+    pub fn instrument_text_trimmed(&self) -> String {
+        self.instrument_text
+            .trim()
+            .lines()
+            .map(|line| line.trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Splits the instrument text up into one string per line, which is one per instrument (or
+    /// sample) slot in the module. The line breaks around the whole text are left out since
+    /// they come from the XML and not the module, and whitespace at the end of each line is cut
+    /// off (leading whitespace is kept, people line their text up with it). Empty lines are
+    /// kept as empty strings, they're empty slots and taking them out would shift the index of
+    /// every instrument after them, so `instruments()[0]` is always the first slot. A module
+    /// without any instrument text gives you an empty vector.
+    pub fn instruments(&self) -> Vec<String> {
+        let text = self.instrument_text.trim_matches(|c| c == '\n' || c == '\r');
+        if text.trim().is_empty() {
+            return Vec::new();
+        }
+
+        text.lines()
+            .map(|line| line.trim_end().to_string())
+            .collect()
+    }
+
+    /// Gives you the format of the module as a [`Format`], so you can `match` on it instead of
+    /// comparing strings.
+    pub fn format_enum(&self) -> Format {
+        Format::from(self.format.as_str())
+    }
+
+    /// Gives you the genre of the module as a [`Genre`], so you can group and filter modules
+    /// without comparing strings.
+    pub fn genre_enum(&self) -> Genre {
+        Genre::from_text(&self.genre)
+    }
+
+    /// Gives you the `scrape_time` (when the info was fetched) as a [`DateTime`], handy for
+    /// working out how stale the numbers are. Only returns [`None`] if `scrape_time` was changed
+    /// to something that isn't an ISO 8601 date.
+    pub fn scrape_time_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.scrape_time)
+            .ok()
+            .map(|date| date.with_timezone(&Utc))
+    }
+
+    /// Gives you the upload date as a [`DateTime`] so modules can be compared chronologically,
+    /// returns [`None`] if the date isn't in a format Mod Archive is known to use.
+    pub fn upload_date_parsed(&self) -> Option<DateTime<Utc>> {
+        parse_modarchive_date(&self.upload_date)
+    }
+
+    /// The filename of the module, same as [`ModInfo::filename`] without having to clone it.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// The title of the module, same as [`ModInfo::title`] without having to clone it.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The format of the module as Mod Archive writes it, same as [`ModInfo::format`] without
+    /// having to clone it, see [`ModInfo::format_enum()`] for a typed version.
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    /// The genre of the module as Mod Archive writes it, same as [`ModInfo::genre`] without
+    /// having to clone it, see [`ModInfo::genre_enum()`] for a typed version.
+    pub fn genre(&self) -> &str {
+        &self.genre
+    }
+
+    /// Tells you if the module is in the spotlight on Mod Archive (a.k.a featured).
+    pub fn is_spotlit(&self) -> bool {
+        self.spotlit
+    }
+
+    /// Tells you if the module has been downloaded more than 10,000 times, which only a small
+    /// portion of the archive has.
+    pub fn is_popular(&self) -> bool {
+        self.download_count > 10_000
+    }
+
+    /// Gives you every format Mod Archive hosts (e.g. for a filter UI), it's the same list as
+    /// [`Format::ALL`] so it never drifts from what [`ModInfo::format_enum()`] recognizes. Use
+    /// [`Format`]'s [`Display`](std::fmt::Display) to get the names the site uses (`XM`, `669`, ...).
+    pub fn known_formats() -> &'static [Format] {
+        Format::ALL
+    }
+
+    /// Tells you if the module is a ProTracker style `MOD` file, see [`ModInfo::format_enum()`]
+    /// for checking the other formats.
+    pub fn is_mod_format(&self) -> bool {
+        self.format_enum() == Format::Mod
+    }
+
+    /// Gives you the download count, favourite count and rating of the module together as a
+    /// [`ModStats`], handy for reporting code that only cares about those.
+    pub fn stats(&self) -> ModStats {
+        ModStats {
+            downloads: self.download_count,
+            favourites: self.fav_count,
+            rating: self.average_rating,
+        }
+    }
+
+    /// Saves the module info as JSON to the given path (only here with the `serde` feature
+    /// enabled), so you can cache it instead of asking Mod Archive again, see
+    /// [`ModInfo::load_json()`] for reading it back.
+    #[cfg(feature = "serde")]
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<(), crate::Error> {
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(crate::Error::SerializationError(e));
+            }
+        };
+
+        match std::fs::write(path, json) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(crate::Error::IOError(e)),
+        }
+    }
+
+    /// Reads back module info saved with [`ModInfo::save_json()`] (only here with the `serde`
+    /// feature enabled).
+    #[cfg(feature = "serde")]
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<ModInfo, crate::Error> {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(crate::Error::IOError(e));
+            }
+        };
+
+        match serde_json::from_str(&json) {
+            Ok(modinfo) => Ok(modinfo),
+            Err(e) => Err(crate::Error::SerializationError(e)),
+        }
+    }
+
+    /// Gives you the MD5 hash of the given bytes as lowercase hexadecimal, the same way Mod
+    /// Archive writes it, so a file you already have can be compared against [`ModInfo::md5`].
+    pub fn md5_of(bytes: &[u8]) -> String {
+        format!("{:x}", md5::compute(bytes))
+    }
+
+    /// Return the raw bytes of a module file into a vector of bytes, modules bigger than 64MB give
+    /// you an [`Error::SizeLimitExceeded`], see [`ModInfo::download_module_with_limit()`] to change
+    /// that limit. Redirects are followed (up to 5 of them), and if what comes back is an HTML
+    /// error page instead of the module you get an [`Error::MalformedResponse`].
+    pub fn download_module(&self) -> Result<Vec<u8>, crate::Error> {
+        self.download_module_with_limit(MAX_MODULE_SIZE)
+    }
+
+    /// Identical to [`ModInfo::download_module()`], but with your own limit on how many bytes the
+    /// module can be, if it's any bigger you get an [`Error::SizeLimitExceeded`] instead of the
+    /// data being cut off.
+    pub fn download_module_with_limit(&self, max_bytes: u64) -> Result<Vec<u8>, crate::Error> {
+        Client::default().download_module_with_limit(self, max_bytes)
+    }
+
+    /// Downloads a module straight from its ID and filename without needing its full [`ModInfo`]
+    /// (and so without spending a request on [`ModInfo::get()`]), handy when you got both from a
+    /// search already. See [`ModSearchResolve::download()`] for doing that on a search result.
+    pub fn download_by_id(id: u32, filename: &str) -> Result<Vec<u8>, crate::Error> {
+        Client::default().download_by_id(id, filename)
+    }
+
+    /// Identical to [`ModInfo::download_module()`], but `progress` gets called with the amount of
+    /// bytes downloaded so far and the total size (if the server told us) as the module comes
+    /// in, handy for progress bars.
+    pub fn download_with_progress(
+        &self,
+        progress: impl FnMut(u64, Option<u64>)
+    ) -> Result<Vec<u8>, crate::Error> {
+        Client::default().download_with_progress(self, progress)
+    }
+
+    /// Identical to [`ModInfo::download_module()`], except the MD5 hash of the downloaded bytes is
+    /// checked against the one Mod Archive gave us, so a corrupted or cut off download gives you
+    /// an [`Error::ChecksumMismatch`] instead of silently succeeding.
+    pub fn download_verified(&self) -> Result<Vec<u8>, crate::Error> {
+        Client::default().download_verified(self)
+    }
+
+    /// Identical to [`ModInfo::download_module()`], but if the module's MD5 hash is the same as
+    /// `known_md5` (e.g. the one you saved the last time you downloaded it) nothing gets
+    /// downloaded and you get [`None`] back. A module with the same hash is the same file, so
+    /// there's no point in fetching it again.
+    pub fn download_if_changed(&self, known_md5: &str) -> Result<Option<Vec<u8>>, crate::Error> {
+        Client::default().download_if_changed(self, known_md5)
+    }
+
+    /// Searches for the filename and downloads every module on the first page of results into
+    /// `dir` (e.g. to grab every version of a song), each one is saved as `{id}_{filename}` and
+    /// you get the paths back in the same order as the search results. A module that's already
+    /// in `dir` with the right size and MD5 hash isn't downloaded again. One module failing
+    /// doesn't stop the rest, you get its error in its place instead (e.g. an
+    /// [`Error::SizeLimitExceeded`] for one bigger than 64MB, which isn't kept), the outer error
+    /// is only for when the search itself fails.
+    pub fn download_search_results<P: AsRef<Path>>(
+        filename: &str,
+        dir: P,
+        api_key: &str
+    ) -> Result<Vec<Result<PathBuf, crate::Error>>, crate::Error> {
+        Client::new(api_key).download_search_results(filename, dir)
+    }
+
+    /// Carries on with a download that got cut off, only asking for the part that's missing from
+    /// `existing` (which has to be opened for reading and writing, e.g. with
+    /// [`std::fs::OpenOptions`]) and adding it to the end. If the server doesn't support that,
+    /// the whole module gets downloaded again and replaces what's in the file. A file that's
+    /// already as big as [`ModInfo::size_bytes`] is left alone, and if it isn't that big once the
+    /// download is done you get an [`Error::IOError`] with [`std::io::ErrorKind::UnexpectedEof`].
+    /// A module that turns out to be bigger than 64MB gives you an [`Error::SizeLimitExceeded`]
+    /// and the file is cut back to what was in it before.
+    pub fn download_resume(&self, existing: &mut std::fs::File) -> Result<(), crate::Error> {
+        Client::default().download_resume(self, existing)
+    }
+
+    /// Streams the module file straight into a file at the given path instead of holding all of
+    /// it in memory, the file is created if it doesn't exist and truncated if it does. Gives you
+    /// the amount of bytes written. A module bigger than 64MB gives you an
+    /// [`Error::SizeLimitExceeded`] and the file is removed again instead of being left cut off.
+    pub fn download_to<P: AsRef<Path>>(&self, path: P) -> Result<u64, crate::Error> {
+        Client::default().download_to(self, path)
+    }
+
+    /// Searches for your string on Mod Archive and returns the results on the first page (a.k.a
+    /// only up to the first 40) as a vector of [`ModSearchResolve`], use
+    /// [`ModInfo::resolve_filename_page()`] to get the ones on the other pages. A search that
+    /// matches nothing gives you an empty vector, [`Error::NotFound`] is only for when the search
+    /// page itself couldn't be found. The results are in the order the site lists them, use
+    /// [`ModSearchResolve::sort_results()`] if you need them in a fixed order. The page is read
+    /// with the same timeout as every other request, and one bigger than 4MB gives you an
+    /// [`Error::SizeLimitExceeded`] instead of being read on forever.
+    pub fn resolve_filename(filename: &str) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::default().resolve_filename(filename)
+    }
+
+    /// Searches for the filename and gives you the full [`ModInfo`] of the best match, which is
+    /// the result with exactly that filename (ignoring case) if there is one and the first result
+    /// otherwise. A search without any results gives you [`Error::NotFound`] instead of leaving
+    /// you to index into an empty vector. Searching doesn't need the API key, fetching the
+    /// module does.
+    pub fn resolve_one(filename: &str, api_key: &str) -> Result<ModInfo, crate::Error> {
+        Client::new(api_key).resolve_one(filename)
+    }
+
+    /// Identical to [`ModInfo::resolve_filename()`], but only the results whose filename is exactly
+    /// the one you gave are kept instead of every near match the search finds. With
+    /// `ignore_case` set `NOWAY.S3M` matches `noway.s3m` too.
+    pub fn resolve_filename_exact(
+        filename: &str,
+        ignore_case: bool
+    ) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::default().resolve_filename_exact(filename, ignore_case)
+    }
+
+    /// Identical to [`ModInfo::resolve_filename()`], but only the results in the given format are
+    /// kept. This goes by the file extension of each result (see
+    /// [`ModSearchResolve::extension_format()`]), not the format Mod Archive verified, so it
+    /// doesn't cost any extra requests.
+    pub fn resolve_filename_by_format(
+        filename: &str,
+        format: Format
+    ) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::default().resolve_filename_by_format(filename, format)
+    }
+
+    /// Identical to [`ModInfo::resolve_filename()`], but gives you the results on the given page
+    /// (starting from 1) along with the total amount of pages so you know when to stop.
+    pub fn resolve_filename_page(filename: &str, page: u32) -> Result<SearchResults, crate::Error> {
+        Client::default().resolve_filename_page(filename, page)
+    }
+
+    /// Identical to [`ModInfo::resolve_filename()`], but you get to pick what the search looks
+    /// at, like the module title instead of the filename.
+    pub fn resolve(query: &str, search_type: SearchType) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        Client::default().resolve(query, search_type)
+    }
+
+    /// Identical to [`ModInfo::resolve_filename_page()`], but you get to pick what the search
+    /// looks at, like the module title instead of the filename.
+    pub fn resolve_page(
+        query: &str,
+        search_type: SearchType,
+        page: u32
+    ) -> Result<SearchResults, crate::Error> {
+        Client::default().resolve_page(query, search_type, page)
+    }
+
+    /// Goes through every result of a search as an iterator, the pages are fetched one at a time
+    /// only once you get to them so stopping early (e.g. with `.take(10)`) doesn't request pages
+    /// you never look at. An error ends the iteration after it's given to you.
+    pub fn resolve_all(query: &str, search_type: SearchType) -> ResolveAll {
+        Client::default().resolve_all(query, search_type)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _parse_search_page(body: &str, page: u32) -> Result<SearchResults, crate::Error> {
+        let dom = match tl::parse(body, tl::ParserOptions::default()) {
+            Ok(dom) => dom,
+            Err(e) => {
+                return Err(crate::Error::HTMLParsingError(e));
+            }
+        };
+        let parser = dom.parser();
+
+        // every search page has this heading, if it's gone the site most likely got redesigned
+        let heading = dom
+            .query_selector("h1.site-wide-page-head-title")
+            .and_then(|mut headings| headings.next())
+            .and_then(|nodehandle| nodehandle.get(parser))
+            .map(|node| node.inner_text(parser).to_lowercase());
+
+        let heading = match heading {
+            Some(heading) => heading,
+            None => {
+                return Err(
+                    crate::Error::ScrapeError(
+                        "the h1.site-wide-page-head-title heading is missing from the search page".into()
+                    )
+                );
+            }
+        };
+
+        // the site's own error pages have the same heading, just with the error in it
+        if heading.contains("error") || heading.contains("not found") {
+            return Err(crate::Error::NotFound);
+        }
+
+        let anchors = match dom.query_selector("a.standard-link[title]") {
+            Some(anchors) => anchors,
+            None => {
+                return Err(
+                    crate::Error::ScrapeError("the a.standard-link[title] selector is invalid".into())
+                );
+            }
+        };
+
+        let mut links: Vec<ModSearchResolve> = Vec::new();
+        for nodehandle in anchors {
+            let node = match nodehandle.get(parser) {
+                Some(node) => node,
+                None => {
+                    return Err(
+                        crate::Error::ScrapeError(
+                            "a search result link points to a node that doesn't exist".into()
+                        )
+                    );
+                }
+            };
+
+            let href = node
+                .as_tag()
+                .and_then(|tag| tag.attributes().get("href").flatten())
+                .map(|href| href.as_utf8_str().to_string())
+                .unwrap_or_default();
+
+            let id = match href.split("query=").nth(1).and_then(|id| id.parse::<u32>().ok()) {
+                Some(id) => id,
+                None => {
+                    return Err(
+                        crate::Error::ScrapeError(
+                            format!("couldn't find a module ID in the search result link {:?}", href)
+                        )
+                    );
+                }
+            };
+
+            let filename = node.inner_text(parser).into();
+
+            // the title attribute is HTML escaped, so the odd one with an & in it needs decoding
+            let title = node
+                .as_tag()
+                .and_then(|tag| tag.attributes().get("title").flatten())
+                .map(|title| {
+                    let title = title.as_utf8_str();
+                    escaper::decode_html(&title).unwrap_or_else(|_| title.to_string())
+                })
+                .unwrap_or_default();
+
+            links.push(ModSearchResolve { id, filename, title });
+        }
+
+        // the pagination links point to the other pages, so the highest one is the last page
+        let total_pages = dom
+            .query_selector("a[href]")
+            .into_iter()
+            .flatten()
+            .filter_map(|nodehandle| {
+                let node = nodehandle.get(parser)?;
+                let href = node.as_tag()?.attributes().get("href")??.as_utf8_str();
+                let page = href.split("page=").nth(1)?;
+                page.split('&').next()?.parse::<u32>().ok()
+            })
+            .fold(page, u32::max);
+
+        // the count is somewhere in the text of the page, it might not be there at all though
+        let total_results = dom
+            .nodes()
+            .iter()
+            .filter_map(|node| node.as_raw())
+            .find_map(|text| parse_result_count(&text.as_utf8_str()))
+            .unwrap_or(links.len() as u32);
+
+        Ok(SearchResults { items: links, total_results, total_pages })
+    }
+
+    /// Scrapes the module's page on the Mod Archive website for what the XML API doesn't give us
+    /// (the tags for now), this is a separate request to a much heavier page so [`ModInfo::get()`]
+    /// doesn't do it for you. Only call it when you actually need the extra data.
+    pub fn enrich_from_page(&mut self) -> Result<(), crate::Error> {
+        Client::default().enrich_from_page(self)
+    }
+
+    /// Gets what the module's web page says about it instead of asking the XML API, so it
+    /// doesn't need an API key or use up any of its quota. Handy when you're running low, but
+    /// the page has a lot less in it so only these get filled in: `id`, `filename`, `title`,
+    /// `size`, `md5`, `format`, `download_count`, `fav_count`, `channel_count`, `genre`,
+    /// `upload_date`, `tags` and `scrape_time`. Everything else is left empty, zero or [`None`]
+    /// (e.g. `instrument_text`, `artist` and `size_bytes`), use [`ModInfo::get()`] for the full
+    /// info.
+    pub fn get_scraped(mod_id: u32) -> Result<ModInfo, crate::Error> {
+        Client::default().get_scraped(mod_id)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _parse_scraped_page(body: &str, mod_id: u32) -> Result<ModInfo, crate::Error> {
+        let scrape_time = iso8601_time(&std::time::SystemTime::now());
+
+        let dom = match tl::parse(body, tl::ParserOptions::default()) {
+            Ok(dom) => dom,
+            Err(e) => {
+                return Err(crate::Error::HTMLParsingError(e));
+            }
+        };
+        let parser = dom.parser();
+
+        let heading = dom
+            .query_selector("h1")
+            .and_then(|mut headings| headings.next())
+            .and_then(|nodehandle| nodehandle.get(parser))
+            .map(|node| escaper::decode_html(&node.inner_text(parser)).unwrap_or_default());
+
+        let heading = match heading {
+            Some(heading) => heading,
+            None => {
+                return Err(
+                    crate::Error::ScrapeError("the h1 heading is missing from the module page".into())
+                );
+            }
+        };
+
+        // the download link ends with the filename, e.g. downloads.php?moduleid=61772#7th_dance.xm
+        let filename = dom
+            .query_selector("a[href]")
+            .into_iter()
+            .flatten()
+            .filter_map(|nodehandle| {
+                let href = nodehandle.get(parser)?.as_tag()?.attributes().get("href")??.as_utf8_str();
+                if !href.contains("downloads.php?moduleid=") {
+                    return None;
+                }
+                href.split_once('#').map(|(_, filename)| filename.to_string())
+            })
+            .next();
+
+        // the heading has the module's title in it, so it can't tell an error page apart (think
+        // of a module called "Terror"). The site's own error pages have no module info at all
+        let has_info = dom
+            .query_selector("div.mod-page-archive-info")
+            .and_then(|mut infos| infos.next())
+            .is_some();
+
+        let filename = match filename {
+            Some(filename) => filename,
+            None if !has_info => {
+                return Err(crate::Error::NotFound);
+            }
+            None => {
+                return Err(
+                    crate::Error::ScrapeError("the download link is missing from the module page".into())
+                );
+            }
+        };
+
+        // the heading is the title followed by the filename in brackets
+        let title = heading.replace(&format!("({})", filename), "").trim().to_string();
+
+        // the rest is listed as "Label: value"
+        let details: Vec<(String, String)> = dom
+            .query_selector("li")
+            .into_iter()
+            .flatten()
+            .filter_map(|nodehandle| {
+                let text = nodehandle.get(parser)?.inner_text(parser);
+                let (label, value) = text.split_once(':')?;
+                Some((label.trim().to_lowercase(), value.trim().to_string()))
+            })
+            .collect();
+        let detail = |label: &str| {
+            details
+                .iter()
+                .find(|(name, _)| name == label)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        };
+        // counts can have thousands separators or words after them ("5 times")
+        let count = |label: &str| {
+            detail(label)
+                .split_whitespace()
+                .next()
+                .map(|number| number.replace(',', ""))
+                .and_then(|number| number.parse::<u32>().ok())
+        };
+
+        let format = match detail("format") {
+            format if !format.is_empty() => format,
+            _ =>
+                Path::new(&filename)
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_uppercase())
+                    .unwrap_or_default(),
+        };
+
+        let tags = match ModInfo::_parse_module_page(body) {
+            Ok(tags) => tags,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        Ok(ModInfo {
+            id: mod_id,
+            filename,
+            title,
+            size: detail("size"),
+            size_bytes: 0,
+            md5: detail("md5"),
+            format,
+            spotlit: false,
+            download_count: count("downloads").unwrap_or_default(),
+            fav_count: count("favourited").unwrap_or_default(),
+            scrape_time,
+            channel_count: count("channels").unwrap_or_default(),
+            genre: detail("genre"),
+            upload_date: detail("uploaded"),
+            instrument_text: String::new(),
+            average_rating: None,
+            review_count: 0,
+            artist: None,
+            artist_id: None,
+            uploader: None,
+            license: None,
+            tags,
+            instrument_count: None,
+            sample_count: None,
+            pattern_count: None,
+            order_count: None,
+            raw_download_count: count("downloads"),
+            raw_fav_count: count("favourited"),
+        })
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _parse_module_page(body: &str) -> Result<Vec<String>, crate::Error> {
+        let dom = match tl::parse(body, tl::ParserOptions::default()) {
+            Ok(dom) => dom,
+            Err(e) => {
+                return Err(crate::Error::HTMLParsingError(e));
+            }
+        };
+        let parser = dom.parser();
+
+        // every tag links to a search for the modules with that tag
+        let mut seen = std::collections::HashSet::new();
+        let tags: Vec<String> = dom
+            .query_selector("a[href]")
+            .into_iter()
+            .flatten()
+            .filter_map(|nodehandle| {
+                let node = nodehandle.get(parser)?;
+                let href = node.as_tag()?.attributes().get("href")??.as_utf8_str();
+                if !href.contains("search_type=tags") {
+                    return None;
+                }
+                let tag = node.inner_text(parser).trim().to_string();
+                (!tag.is_empty()).then_some(tag)
+            })
+            // a tag can be linked more than once anywhere on the page, only the first one is kept
+            .filter(|tag| seen.insert(tag.clone()))
+            .collect();
+
+        Ok(tags)
+    }
+
+    /// Tells you how many requests have been made with the given API key so far as a
+    /// [`RequestQuota`], so you can check `current < maximum` before making a batch of requests.
+    /// A response without both counts in it gives you [`Error::MalformedResponse`].
+    pub fn request_quota(api_key: &str) -> Result<RequestQuota, crate::Error> {
+        Client::new(api_key).request_quota()
+    }
+
+    /// Tells you how many requests have been made with the given API key so far. A key Mod
+    /// Archive doesn't accept gives you [`Error::InvalidApiKey`], while not being able to reach Mod
+    /// Archive at all gives you [`Error::APIRequestError`].
+    pub fn track_requests(api_key: &str) -> Result<String, crate::Error> {
+        Client::new(api_key).track_requests()
+    }
+}
+
+impl TryFrom<u32> for ModInfo {
+    type Error = crate::Error;
+
+    /// Fetches the module with the given ID using the API key in the `MODARCH_KEY` environment
+    /// variable, so quick scripts can just do `let modinfo: ModInfo = 51772.try_into()?;`. If the
+    /// variable isn't set you get an [`Error::InvalidApiKey`], use [`ModInfo::get()`] to pass the
+    /// key yourself.
+    fn try_from(mod_id: u32) -> Result<ModInfo, crate::Error> {
+        let api_key = std::env::var("MODARCH_KEY").unwrap_or_default();
+        ModInfo::get(mod_id, &api_key)
+    }
+}
+
+impl std::fmt::Display for ModInfo {
+    /// A short human readable summary of the module, e.g.
+    /// `7th Dance (7th_dance.xm) — XM, 16ch, 3021 downloads`, modules without a title just show
+    /// their filename.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = self.title.trim();
+        if title.is_empty() {
+            write!(f, "{}", self.filename)?;
+        } else {
+            write!(f, "{} ({})", title, self.filename)?;
+        }
+
+        let format = if self.format.is_empty() { "unknown format" } else { self.format.as_str() };
+        write!(f, " — {}, {}ch, {} downloads", format, self.channel_count, self.download_count)
+    }
+}
+
+impl ModSearchResolve {
+    /// Get the download link of this specific module.
+    pub fn get_download_link(&self) -> String {
+        download_link(self.id, &self.filename)
+    }
+
+    /// Guesses the format of the module from the extension of its filename, `noway.s3m` gives you
+    /// [`Format::S3m`]. A filename without an extension gives you an empty [`Format::Other`].
+    pub fn extension_format(&self) -> Format {
+        let extension = Path::new(&self.filename)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Format::from(extension.as_str())
+    }
+
+    /// Fetches the full [`ModInfo`] of this search result, saves you picking the ID out and
+    /// passing it to [`ModInfo::get()`] yourself.
+    pub fn get(&self, api_key: &str) -> Result<ModInfo, crate::Error> {
+        ModInfo::get(self.id, api_key)
+    }
+
+    /// Downloads this module without fetching its full info first, see
+    /// [`ModInfo::download_by_id()`].
+    pub fn download(&self) -> Result<Vec<u8>, crate::Error> {
+        ModInfo::download_by_id(self.id, &self.filename)
+    }
+
+    /// Sorts search results by the given [`SortKey`], results that compare equal (e.g. the same
+    /// filename) are put in order of their ID so the order is always the same.
+    pub fn sort_results(results: &mut [ModSearchResolve], key: SortKey) {
+        match key {
+            SortKey::Id => results.sort_by_key(|result| result.id),
+            SortKey::Filename =>
+                results.sort_by_cached_key(|result| (result.filename.to_lowercase(), result.id)),
+            SortKey::Title =>
+                results.sort_by_cached_key(|result| (result.title.to_lowercase(), result.id)),
+        }
+    }
+}
+
+impl PartialEq for ModSearchResolve {
+    /// Search results are the same module if they have the same ID
+    fn eq(&self, other: &ModSearchResolve) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for ModSearchResolve {}
+
+impl std::hash::Hash for ModSearchResolve {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PartialOrd for ModSearchResolve {
+    fn partial_cmp(&self, other: &ModSearchResolve) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ModSearchResolve {
+    /// Search results are ordered by their ID, lowest first
+    fn cmp(&self, other: &ModSearchResolve) -> std::cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+/// Wraps a [`ModInfo`] so it's compared, hashed and ordered by its ID alone, for putting
+/// modules in a `HashSet`/`HashMap` or a `BTreeSet`. [`ModInfo`] itself compares every field
+/// (and has an `f32` in it), so it can't be [`Eq`]. The fields can still be reached through it
+/// like `wrapped.filename`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ModInfoById(pub ModInfo);
+
+impl From<ModInfo> for ModInfoById {
+    fn from(modinfo: ModInfo) -> ModInfoById {
+        ModInfoById(modinfo)
+    }
+}
+
+impl std::ops::Deref for ModInfoById {
+    type Target = ModInfo;
+
+    fn deref(&self) -> &ModInfo {
+        &self.0
+    }
+}
+
+impl PartialEq for ModInfoById {
+    /// The same module if they have the same ID, even if they were fetched at different times
+    fn eq(&self, other: &ModInfoById) -> bool {
+        self.0.id == other.0.id
+    }
+}
+
+impl Eq for ModInfoById {}
+
+impl std::hash::Hash for ModInfoById {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.id.hash(state);
+    }
+}
+
+impl PartialOrd for ModInfoById {
+    fn partial_cmp(&self, other: &ModInfoById) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ModInfoById {
+    /// Ordered by their ID, lowest first
+    fn cmp(&self, other: &ModInfoById) -> std::cmp::Ordering {
+        self.0.id.cmp(&other.0.id)
+    }
+}
+
+impl SearchResults {
+    /// Sorts the results on this page by the given [`SortKey`], see
+    /// [`ModSearchResolve::sort_results()`].
+    pub fn sort(&mut self, key: SortKey) {
+        ModSearchResolve::sort_results(&mut self.items, key);
     }
 }
 
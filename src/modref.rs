@@ -0,0 +1,40 @@
+use crate::ModInfo;
+
+/// A way to point at a module, either by its ID or its filename, handy for taking whichever one
+/// the user typed in. Parse one from a string with [`str::parse()`], anything made of only
+/// digits is an ID and the rest are filenames.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModRef {
+    /// The module ID on Mod Archive
+    Id(u32),
+    /// The filename of the module, like `noway.s3m`
+    Filename(String),
+}
+
+impl std::str::FromStr for ModRef {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<ModRef, Self::Err> {
+        let s = s.trim();
+        if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+            if let Ok(id) = s.parse::<u32>() {
+                return Ok(ModRef::Id(id));
+            }
+        }
+
+        Ok(ModRef::Filename(s.to_string()))
+    }
+}
+
+impl ModRef {
+    /// Fetches the full [`ModInfo`] of the module, a filename is resolved first (preferring a
+    /// result with the exact same filename over the first one) which doesn't need the API key.
+    /// A filename without any results gives you [`Error::NotFound`](crate::Error::NotFound).
+    pub fn resolve(&self, api_key: &str) -> Result<ModInfo, crate::Error> {
+        match self {
+            ModRef::Id(id) => ModInfo::get(*id, api_key),
+            ModRef::Filename(filename) => ModInfo::resolve_one(filename, api_key),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! The types you'll be using most of the time, so `use modark::prelude::*;` is all you need to
+//! get going.
+
+#[cfg(feature = "async")]
+pub use crate::AsyncClient;
+pub use crate::{
+    CachedClient,
+    Client,
+    Error,
+    ErrorKind,
+    Format,
+    Genre,
+    ModInfo,
+    ModInfoById,
+    ModRef,
+    ModSearch,
+    ModSearchResolve,
+    Review,
+    SearchResults,
+    SearchType,
+    SortKey,
+};
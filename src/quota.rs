@@ -0,0 +1,100 @@
+use crate::Client;
+use std::sync::{ Arc, Mutex };
+use std::time::{ Duration, Instant };
+
+/// How long the quota Mod Archive told us about is trusted before asking again
+const QUOTA_RECHECK: Duration = Duration::from_secs(300);
+
+/// What a client with [`Client::with_quota_guard()`] does once its API key has no requests left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuotaGuard {
+    /// Gives you an [`Error::RateLimited`](crate::Error::RateLimited) without making the request
+    Error,
+    /// Blocks the thread until Mod Archive says there are requests left again, checking every 5
+    /// minutes
+    Wait,
+}
+
+/// The requests a guarded client thinks it has left, shared between its clones
+#[derive(Debug)]
+pub(crate) struct QuotaState {
+    guard: QuotaGuard,
+    /// How many requests are left and when Mod Archive last told us, [`None`] until the first
+    /// request
+    remaining: Mutex<Option<(u32, Instant)>>,
+}
+
+impl QuotaState {
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _remaining(&self) -> std::sync::MutexGuard<'_, Option<(u32, Instant)>> {
+        // a thread panicking while holding the lock can't leave the count half updated
+        self.remaining.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Client {
+    /// Keeps track of how many requests the API key has left (asking Mod Archive with
+    /// [`Client::request_quota()`] before the first request and every 5 minutes after that), and
+    /// once there are none left it does whatever the [`QuotaGuard`] says instead of sending the
+    /// request anyway. Only the requests that need the API key are counted. Clones of the client
+    /// share the count. If the quota can't be fetched (or doesn't make sense) the request isn't
+    /// sent and you get that error instead, the quota is asked for again on the next request.
+    pub fn with_quota_guard(mut self, guard: QuotaGuard) -> Client {
+        self.quota = Some(Arc::new(QuotaState { guard, remaining: Mutex::new(None) }));
+        self
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    pub(crate) fn _check_quota(&self) -> Result<(), crate::Error> {
+        let state = match &self.quota {
+            Some(state) => state,
+            None => {
+                return Ok(());
+            }
+        };
+
+        loop {
+            // only look at the count while holding the lock, it's let go of before asking Mod
+            // Archive so the other clones aren't stuck waiting on that request
+            let checked = {
+                let mut remaining = state._remaining();
+                match *remaining {
+                    Some((left, checked)) if checked.elapsed() < QUOTA_RECHECK => {
+                        if left > 0 {
+                            *remaining = Some((left - 1, checked));
+                            return Ok(());
+                        }
+                        Some(checked)
+                    }
+                    _ => None,
+                }
+            };
+
+            match checked {
+                Some(checked) =>
+                    match state.guard {
+                        QuotaGuard::Error => {
+                            return Err(crate::Error::RateLimited);
+                        }
+                        QuotaGuard::Wait => {
+                            log::warn!("Out of API requests, waiting before checking the quota again");
+                            std::thread::sleep(QUOTA_RECHECK.saturating_sub(checked.elapsed()));
+                        }
+                    }
+                None => {
+                    let quota = match self.request_quota() {
+                        Ok(quota) => quota,
+                        Err(e) => {
+                            return Err(e);
+                        }
+                    };
+
+                    // the count gets taken from on the next time around, under the lock
+                    let left = quota.maximum.saturating_sub(quota.current);
+                    *state._remaining() = Some((left, Instant::now()));
+                }
+            }
+        }
+    }
+}
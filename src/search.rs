@@ -1,19 +1,316 @@
+use crate::api_error;
+use crate::chart_page_url;
+use crate::find_node_text;
+use crate::latest_additions_url;
+use crate::parse_xml;
+use crate::read_page;
+use crate::Client;
+use crate::ModInfo;
 use crate::ModSearch;
-use crate::BASEURL;
+use crate::ModSearchResolve;
+use crate::SearchResults;
+use crate::SearchType;
+use crate::MAX_PAGE_SIZE;
+
+impl Default for ModSearch {
+    /// A search by filename with an empty query and none of the optional fields set, so only the
+    /// query has to be filled in:
+    /// `ModSearch { searchquery: "noway".into(), ..Default::default() }`
+    fn default() -> ModSearch {
+        ModSearch {
+            searchtype: "filename".into(),
+            searchquery: String::new(),
+            searchpage: None,
+            searchformat: None,
+            searchsize: None,
+            searchchannels: None,
+        }
+    }
+}
 
 impl ModSearch {
-    // TODO: the rest of the search functions
+    /// Runs the search on Mod Archive and returns the modules it found as [`SearchResults`],
+    /// along with the total amount of results and pages. The optional fields are only sent along
+    /// when they're set.
+    pub fn search(&self, api_key: &str) -> Result<SearchResults, crate::Error> {
+        Client::new(api_key).search(self)
+    }
+
+    /// Only look for modules with at least `min` channels, sets `searchchannels` to `min-`.
+    pub fn channels_min(mut self, min: u32) -> ModSearch {
+        self.searchchannels = Some(format!("{}-", min));
+        self
+    }
+
+    /// Only look for modules with `min` up to `max` channels, sets `searchchannels` to
+    /// `min-max`.
+    ///
+    /// Panics if `min` is bigger than `max`.
+    pub fn channels_range(mut self, min: u32, max: u32) -> ModSearch {
+        assert!(min <= max, "the minimum channel count ({min}) is bigger than the maximum ({max})");
+        self.searchchannels = Some(format!("{}-{}", min, max));
+        self
+    }
+
+    /// Only look for modules with a size (in KB) from `min` up to `max`, sets `searchsize` to
+    /// `min-max`.
+    ///
+    /// Panics if `min` is bigger than `max`.
+    pub fn size_range(mut self, min: u32, max: u32) -> ModSearch {
+        assert!(min <= max, "the minimum size ({min}) is bigger than the maximum ({max})");
+        self.searchsize = Some(format!("{}-{}", min, max));
+        self
+    }
 
     /// (a helper function to make the code more readable, do not use directly)
-    fn _inner_request(request: &str, query: &str, api_key: &str) -> Result<String, crate::Error> {
-        let body = ureq
-            ::get(format!("{BASEURL}?key={api_key}&request={request}&query={query}").as_str())
-            .timeout(std::time::Duration::from_secs(60))
-            .call();
+    pub(crate) fn _params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("type", self.searchtype.clone()),
+            ("query", self.searchquery.clone())
+        ];
+        if let Some(page) = self.searchpage {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(format) = &self.searchformat {
+            params.push(("format", format.clone()));
+        }
+        if let Some(size) = &self.searchsize {
+            params.push(("size", size.clone()));
+        }
+        if let Some(channels) = &self.searchchannels {
+            params.push(("channels", channels.clone()));
+        }
+
+        params
+    }
+}
+
+impl Client {
+    /// Runs a search on Mod Archive, see [`ModSearch::search()`].
+    pub fn search(&self, search: &ModSearch) -> Result<SearchResults, crate::Error> {
+        let params = search._params();
+        let params: Vec<(&str, &str)> = params
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
 
-        match body {
-            Ok(body) => Ok(body.into_string().unwrap_or_default()),
-            Err(e) => Err(crate::Error::APIRequestError(Box::new(e))),
+        let body = match self._inner_request("search", &params) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        parse_module_list(&body)
+    }
+
+    /// Lists the modules in a genre, see [`ModInfo::search_by_genre()`](crate::ModInfo::search_by_genre).
+    pub fn search_by_genre(
+        &self,
+        genre_id: u32,
+        page: u32
+    ) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        let search = ModSearch {
+            searchtype: "genre".into(),
+            searchquery: genre_id.to_string(),
+            searchpage: Some(page),
+            ..Default::default()
+        };
+
+        match self.search(&search) {
+            Ok(results) => Ok(results.items),
+            Err(e) => Err(e),
         }
     }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _website_page(&self, url: &str) -> Result<String, crate::Error> {
+        let body = match self._call(url) {
+            Ok(body) => body,
+            Err(e) if e.http_status() == Some(404) => {
+                return Err(crate::Error::NotFound);
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        read_page(body.into_reader(), MAX_PAGE_SIZE)
+    }
+
+    /// (a helper function to make the code more readable, do not use directly)
+    fn _chart(&self, chart: &str, page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        // the XML API has no charts, so they come off the website's chart pages instead
+        let body = match self._website_page(&chart_page_url(chart, page)) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        parse_chart(&body, page)
+    }
+
+    /// Lists the most downloaded modules, see
+    /// [`ModInfo::top_downloads()`](crate::ModInfo::top_downloads).
+    pub fn top_downloads(&self, page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        self._chart("tophits", page)
+    }
+
+    /// Lists the most favourited modules, see
+    /// [`ModInfo::top_favourites()`](crate::ModInfo::top_favourites).
+    pub fn top_favourites(&self, page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        self._chart("topfavourites", page)
+    }
+
+    /// Lists the modules uploaded most recently, see
+    /// [`ModInfo::latest_additions()`](crate::ModInfo::latest_additions).
+    pub fn latest_additions(&self, page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+        // the XML API has no listing for new uploads, so this comes off the website instead
+        let body = match self._website_page(&latest_additions_url(page)) {
+            Ok(body) => body,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+
+        parse_latest_additions(&body, page)
+    }
+}
+
+/// Parses a chart page of the Mod Archive website, the modules are kept in the order they're
+/// ranked in. It lists them the same way the search page does so the same scraper is used
+pub(crate) fn parse_chart(body: &str, page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+    match ModInfo::_parse_search_page(body, page) {
+        Ok(results) => Ok(results.items),
+        Err(e) => Err(e),
+    }
+}
+
+/// Parses the latest additions page of the Mod Archive website, it lists modules the same way
+/// the search page does so the same scraper is used for both
+pub(crate) fn parse_latest_additions(body: &str, page: u32) -> Result<Vec<ModSearchResolve>, crate::Error> {
+    let mut additions = match ModInfo::_parse_search_page(body, page) {
+        Ok(results) => results.items,
+        Err(e) => {
+            return Err(e);
+        }
+    };
+
+    // module IDs go up with every upload, so the biggest one is the newest
+    additions.sort_by_key(|addition| std::cmp::Reverse(addition.id));
+    Ok(additions)
+}
+
+/// An iterator going through every result of a search, fetching the next page only once the
+/// results on the current one run out, see [`ModInfo::resolve_all()`](crate::ModInfo::resolve_all)
+pub struct ResolveAll {
+    fetch_page: Box<dyn FnMut(u32) -> Result<SearchResults, crate::Error>>,
+    page: u32,
+    total_pages: u32,
+    items: std::vec::IntoIter<ModSearchResolve>,
+    done: bool,
+}
+
+impl ResolveAll {
+    /// (a helper function to make the code more readable, do not use directly)
+    pub(crate) fn _new(
+        fetch_page: impl FnMut(u32) -> Result<SearchResults, crate::Error> + 'static
+    ) -> ResolveAll {
+        ResolveAll {
+            fetch_page: Box::new(fetch_page),
+            page: 1,
+            total_pages: 1,
+            items: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+impl Iterator for ResolveAll {
+    type Item = Result<ModSearchResolve, crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(Ok(item));
+            }
+
+            // the first page is always fetched, after that we know how many there are
+            if self.done || (self.page > 1 && self.page > self.total_pages) {
+                return None;
+            }
+
+            match (self.fetch_page)(self.page) {
+                Ok(results) => {
+                    if results.items.is_empty() {
+                        self.done = true;
+                    }
+                    self.page += 1;
+                    self.total_pages = results.total_pages;
+                    self.items = results.items.into_iter();
+                }
+                // running out of pages isn't an error, the search just ended
+                Err(crate::Error::NotFound) if self.page > 1 => {
+                    self.done = true;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl Client {
+    /// Goes through every result of a search page by page, see
+    /// [`ModInfo::resolve_all()`](crate::ModInfo::resolve_all).
+    pub fn resolve_all(&self, query: &str, search_type: SearchType) -> ResolveAll {
+        let (client, query) = (self.clone(), query.to_string());
+        ResolveAll::_new(move |page| client.resolve_page(&query, search_type, page))
+    }
+}
+
+/// Parses every `<module>` in a response listing modules (searches and the like) into
+/// [`SearchResults`], along with how many pages the listing has
+pub(crate) fn parse_module_list(body: &str) -> Result<SearchResults, crate::Error> {
+    let xml = match parse_xml(body) {
+        Ok(xml) => xml,
+        Err(e) => {
+            return Err(e);
+        }
+    };
+
+    let xml_descendants: Vec<_> = xml.descendants().collect();
+
+    if let Some(error) = find_node_text(&xml_descendants, "error") {
+        return Err(api_error(&error));
+    }
+
+    let total_pages = find_node_text(&xml_descendants, "totalpages")
+        .and_then(|pages| pages.trim().parse::<u32>().ok())
+        .unwrap_or(1);
+
+    let modules: Vec<ModSearchResolve> = xml_descendants
+        .iter()
+        .filter(|node| node.has_tag_name("module"))
+        .map(|node| {
+            // only look at the direct children, the artist info nested inside has IDs too
+            let children: Vec<_> = node.children().collect();
+            let id = find_node_text(&children, "id")
+                .and_then(|id| id.parse::<u32>().ok())
+                .unwrap_or_default();
+            let filename = find_node_text(&children, "filename").unwrap_or_default();
+            let title = find_node_text(&children, "songtitle").unwrap_or_default();
+
+            ModSearchResolve { id, filename, title }
+        })
+        .collect();
+
+    let total_results = find_node_text(&xml_descendants, "results")
+        .and_then(|results| results.trim().parse::<u32>().ok())
+        .unwrap_or(modules.len() as u32);
+
+    Ok(SearchResults { items: modules, total_results, total_pages })
 }
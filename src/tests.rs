@@ -1,50 +1,1264 @@
 #[cfg(test)]
+use crate::Client;
 use crate::ModInfo;
+use crate::ModSearch;
 use std::env;
+use std::io::{ BufRead, BufReader, Write };
+
+/// Spins up a local server which answers each connection with the next response in line, and
+/// gives back its URL
+fn mock_server(responses: Vec<String>) -> String {
+    mock_server_recording(responses).0
+}
+
+/// Identical to [`mock_server`], but also hands back every request the server got (the request
+/// line and headers)
+fn mock_server_recording(responses: Vec<String>) -> (String, std::sync::mpsc::Receiver<String>) {
+    mock_server_bytes(responses.into_iter().map(String::into_bytes).collect())
+}
+
+/// Identical to [`mock_server_recording`], but the responses can be any bytes (like a gzipped
+/// body)
+fn mock_server_bytes(responses: Vec<Vec<u8>>) -> (String, std::sync::mpsc::Receiver<String>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                request.push_str(&line);
+                line.clear();
+            }
+            let _ = sender.send(request);
+
+            stream.write_all(&response).unwrap();
+        }
+    });
+
+    (url, receiver)
+}
+
+/// Gzips a body up into a full HTTP response for [`mock_server_bytes`]
+fn gzip_response(body: &str) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(body.as_bytes()).unwrap();
+    let body = encoder.finish().unwrap();
+
+    let mut response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ).into_bytes();
+    response.extend_from_slice(&body);
+    response
+}
+
+/// Wraps a body up into a full HTTP response for [`mock_server`]
+fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[test]
+fn instr_text() {
+    let instr_text = ModInfo::get(
+        61772,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    ).unwrap().instrument_text;
+    assert_eq!(
+        instr_text,
+        "\n        7th  Dance\n\n             By:\n Jari Ylamaki aka Yrde\n  27.11.2000 HELSINKI\n\n            Finland\n           SITE :\n  www.mp3.com/Yrde"
+    );
+}
+
+#[test]
+fn fixture_parsing() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.id, 61772);
+    assert_eq!(modinfo.filename, "7th_dance.xm");
+    assert_eq!(modinfo.title, "7th Dance");
+    assert_eq!(modinfo.size_bytes, 493630);
+    assert_eq!(modinfo.format, "XM");
+    assert!(!modinfo.spotlit);
+    assert_eq!(modinfo.download_count, 3021);
+    assert_eq!(modinfo.fav_count, 12);
+    assert_eq!(modinfo.channel_count, 16);
+    assert_eq!(modinfo.genre, "Electronic - Dance");
+    assert_eq!(modinfo.upload_date, "Mon 4th Dec 2000");
+    assert!(modinfo.instrument_text.starts_with("\n        7th  Dance"));
+}
+
+#[test]
+fn fixture_ratings() {
+    let fixture = include_str!("../fixtures/view_by_moduleid.xml");
+    let modinfo = ModInfo::from_xml(fixture, 61772).unwrap();
+    assert_eq!(modinfo.average_rating, Some(8.0));
+    assert_eq!(modinfo.review_count, 0);
+
+    let unrated = fixture.replace("<comment_total>3</comment_total>", "<comment_total>0</comment_total>");
+    let modinfo = ModInfo::from_xml(&unrated, 61772).unwrap();
+    assert_eq!(modinfo.average_rating, None);
+}
+
+#[test]
+fn fixture_artist() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.artist.as_deref(), Some("Yrde"));
+    assert_eq!(modinfo.artist_id, Some(69141));
+
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_bare.xml"), 41070).unwrap();
+    assert_eq!(modinfo.artist, None);
+    assert_eq!(modinfo.artist_id, None);
+}
+
+#[test]
+fn fixture_instrument_counts() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.instrument_count, Some(12));
+    assert_eq!(modinfo.sample_count, Some(24));
+
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_bare.xml"), 41070).unwrap();
+    assert_eq!(modinfo.instrument_count, None);
+    assert_eq!(modinfo.sample_count, None);
+}
+
+#[test]
+fn fixture_pattern_counts() {
+    // an XM reports its layout
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.pattern_count, Some(38));
+    assert_eq!(modinfo.order_count, Some(52));
+
+    // the bare MOD doesn't
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_bare.xml"), 41070).unwrap();
+    assert_eq!(modinfo.pattern_count, None);
+    assert_eq!(modinfo.order_count, None);
+}
+
+#[test]
+fn fixture_selected_fields() {
+    use crate::Field;
+
+    let body = include_str!("../fixtures/view_by_moduleid.xml");
+    let partial = crate::PartialModInfo::_parse(body, 61772, &[Field::Title, Field::DownloadCount]).unwrap();
+    let modinfo = ModInfo::from_xml(body, 61772).unwrap();
+    assert_eq!(partial.id, 61772);
+    assert_eq!(partial.title.as_deref(), Some(modinfo.title.as_str()));
+    assert_eq!(partial.download_count, Some(modinfo.download_count));
+    // not asked for
+    assert_eq!(partial.filename, None);
+    assert_eq!(partial.md5, None);
+
+    let partial = crate::PartialModInfo::_parse(body, 61772, &[Field::SizeBytes, Field::Genre]).unwrap();
+    assert_eq!(partial.size_bytes, Some(modinfo.size_bytes));
+    assert_eq!(partial.genre.as_deref(), Some(modinfo.genre.as_str()));
+
+    let truncated = crate::PartialModInfo::_parse(include_str!("../fixtures/truncated.xml"), 61772, &[Field::Title]);
+    assert!(matches!(truncated, Err(crate::Error::MalformedResponse)));
+
+    let not_found = crate::PartialModInfo::_parse(include_str!("../fixtures/not_found.xml"), 61772, &[Field::Title]);
+    assert!(matches!(not_found, Err(crate::Error::NotFound)));
+
+    // without a songtitle both fall back to the title
+    let untitled = body.replace("songtitle>", "title>");
+    let partial = crate::PartialModInfo::_parse(&untitled, 61772, &[Field::Title]).unwrap();
+    assert_eq!(partial.title, Some(ModInfo::from_xml(&untitled, 61772).unwrap().title));
+}
+
+#[test]
+fn mocked_selected_fields() {
+    let url = mock_server(vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))]);
+    let partial = Client::new("mock-key").with_base_url(url).get_fields(61772, &[crate::Field::Md5]).unwrap();
+    assert_eq!(partial.md5.as_deref(), Some("5a6b1e4d0b2c9f8e7d6c5b4a39281706"));
+    assert!(matches!(Client::new("mock-key").get_fields(0, &[crate::Field::Md5]), Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn fixture_reviews() {
+    let reviews = crate::Review::_parse(include_str!("../fixtures/view_by_moduleid_comments.xml")).unwrap();
+    assert_eq!(reviews.len(), 3);
+    assert_eq!(reviews[0].author, "chiptune_fan");
+    assert_eq!(reviews[0].rating, Some(9));
+    assert_eq!(reviews[0].text, "Lovely & calm, the ending is superb.");
+    assert_eq!(reviews[1].rating, None);
+    // out of the 0-10 scale
+    assert_eq!(reviews[2].rating, None);
+
+    // the module's own empty comment isn't a review
+    let reviews = crate::Review::_parse(include_str!("../fixtures/view_by_moduleid_bare.xml")).unwrap();
+    assert!(reviews.is_empty());
+}
+
+#[test]
+fn mocked_get_reviews() {
+    let (url, requests) = mock_server_recording(
+        vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid_comments.xml"))]
+    );
+    let reviews = Client::new("mock-key").with_base_url(url).get_reviews(41070).unwrap();
+    assert_eq!(reviews.len(), 3);
+    assert!(requests.recv().unwrap().contains("&request=view_by_moduleid&query=41070&com=1 "));
+}
+
+#[test]
+fn fixture_missing_counts() {
+    // a genuine zero
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_bare.xml"), 41070).unwrap();
+    assert_eq!(modinfo.raw_download_count, Some(845));
+    assert_eq!(modinfo.raw_fav_count, Some(0));
+
+    // no nodes at all
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_no_counts.xml"), 41070).unwrap();
+    assert_eq!(modinfo.raw_download_count, None);
+    assert_eq!(modinfo.raw_fav_count, None);
+    assert_eq!(modinfo.download_count, 0);
+    assert_eq!(modinfo.fav_count, 0);
+}
+
+#[test]
+fn fixture_uploader() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_uploader.xml"), 61772).unwrap();
+    assert_eq!(modinfo.uploader.as_deref(), Some("chiptune_fan"));
+    // the uploader is listed first, but they aren't the artist
+    assert_eq!(modinfo.artist.as_deref(), Some("Yrde"));
+    assert_eq!(modinfo.artist_id, Some(69141));
+
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.uploader, None);
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_bare.xml"), 41070).unwrap();
+    assert_eq!(modinfo.uploader, None);
+    assert_eq!(modinfo.artist, None);
+}
+
+#[test]
+fn fixture_license() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.license.as_deref(), Some("Public Domain"));
+
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid_bare.xml"), 41070).unwrap();
+    assert_eq!(modinfo.license, None);
+}
+
+#[test]
+fn fixture_scrape_time_utc() {
+    let before = chrono::Utc::now() - chrono::Duration::seconds(1);
+    let mut modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    let scraped = modinfo.scrape_time_utc().unwrap();
+    assert!(scraped >= before && scraped <= chrono::Utc::now());
+
+    modinfo.scrape_time = "not a date".into();
+    assert_eq!(modinfo.scrape_time_utc(), None);
+}
+
+#[test]
+fn fixture_accessors() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.filename(), "7th_dance.xm");
+    assert_eq!(modinfo.title(), "7th Dance");
+    assert_eq!(modinfo.format(), "XM");
+    assert_eq!(modinfo.genre(), "Electronic - Dance");
+}
+
+#[test]
+fn fixture_predicates() {
+    let mut modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert!(!modinfo.is_spotlit());
+    assert!(!modinfo.is_popular());
+    assert!(!modinfo.is_mod_format());
+
+    modinfo.spotlit = true;
+    modinfo.download_count = 10_001;
+    modinfo.format = "MOD".into();
+    assert!(modinfo.is_spotlit());
+    assert!(modinfo.is_popular());
+    assert!(modinfo.is_mod_format());
+}
+
+#[test]
+fn fixture_stats() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(
+        modinfo.stats(),
+        crate::ModStats { downloads: 3021, favourites: 12, rating: Some(8.0) }
+    );
+}
+
+#[test]
+fn md5_of() {
+    assert_eq!(ModInfo::md5_of(b""), "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(ModInfo::md5_of(b"The quick brown fox jumps over the lazy dog"), "9e107d9d372bb6826bd81d3542a419d6");
+}
+
+#[test]
+fn fixture_instrument_text_trimmed() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(
+        modinfo.instrument_text_trimmed(),
+        "7th  Dance\n\nBy:\nJari Ylamaki aka Yrde\n27.11.2000 HELSINKI\n\nFinland\nSITE :\nwww.mp3.com/Yrde"
+    );
+    assert!(modinfo.instrument_text.starts_with('\n'));
+}
+
+#[test]
+fn fixture_instruments() {
+    let mut modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    let instruments = modinfo.instruments();
+    assert_eq!(instruments.len(), modinfo.instrument_text_trimmed().lines().count());
+    assert_eq!(
+        instruments.iter().map(|line| line.trim()).collect::<Vec<_>>().join("\n"),
+        modinfo.instrument_text_trimmed()
+    );
+    assert!(instruments.iter().all(|line| line.trim_end() == line));
+
+    modinfo.instrument_text = "\r\n  kick\r\n\r\nsnare  \r\n".to_string();
+    assert_eq!(modinfo.instruments(), ["  kick", "", "snare"]);
+
+    modinfo.instrument_text = "\n  \n".to_string();
+    assert!(modinfo.instruments().is_empty());
+}
+
+#[test]
+fn fixture_download_link() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(
+        modinfo.get_download_link(),
+        "https://api.modarchive.org/downloads.php?moduleid=61772#7th_dance.xm"
+    );
+}
+
+#[test]
+fn fixture_module_page_url() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(
+        modinfo.module_page_url(),
+        "https://modarchive.org/index.php?request=view_by_moduleid&query=61772"
+    );
+}
+
+#[test]
+fn fixture_display() {
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.to_string(), "7th Dance (7th_dance.xm) — XM, 16ch, 3021 downloads");
+
+    let untitled = include_str!("../fixtures/view_by_moduleid.xml").replace("7th Dance]]", "]]");
+    let modinfo = ModInfo::from_xml(&untitled, 61772).unwrap();
+    assert_eq!(modinfo.to_string(), "7th_dance.xm — XM, 16ch, 3021 downloads");
+}
+
+#[test]
+fn fixture_not_found() {
+    let invalid = ModInfo::from_xml(include_str!("../fixtures/not_found.xml"), 30638);
+    assert!(matches!(invalid, Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn fixture_truncated() {
+    let truncated = ModInfo::from_xml(include_str!("../fixtures/truncated.xml"), 61772);
+    assert!(matches!(truncated, Err(crate::Error::MalformedResponse)));
+}
+
+#[test]
+fn fixture_error_shapes() {
+    let cases = [
+        (include_str!("../fixtures/not_found.xml"), "NotFound"),
+        (include_str!("../fixtures/rate_limited.xml"), "RateLimited"),
+        (include_str!("../fixtures/invalid_key.xml"), "InvalidApiKey"),
+        (include_str!("../fixtures/unknown_error.xml"), "Unknown"),
+    ];
+    for (fixture, expected) in cases {
+        let error = ModInfo::from_xml(fixture, 30638).unwrap_err();
+        assert_eq!(format!("{:?}", error), expected);
+    }
+}
+
+#[test]
+fn mocked_get() {
+    let url = mock_server(
+        vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))]
+    );
+    let modinfo = Client::new("mock-key").with_base_url(url).get(61772).unwrap();
+    assert_eq!(modinfo.filename, "7th_dance.xm");
+    assert_eq!(modinfo.title, "7th Dance");
+}
+
+#[test]
+fn mocked_refresh() {
+    let fixture = include_str!("../fixtures/view_by_moduleid.xml");
+    let url = mock_server(
+        vec![
+            http_response("200 OK", &fixture.replace("<hits>3021</hits>", "<hits>3500</hits>")),
+            http_response("200 OK", include_str!("../fixtures/not_found.xml"))
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url);
+    let mut modinfo = ModInfo::from_xml(fixture, 61772).unwrap();
+    modinfo.scrape_time = "2000-01-01T00:00:00+00:00".into();
+
+    client.refresh(&mut modinfo).unwrap();
+    assert_eq!(modinfo.download_count, 3500);
+    assert_ne!(modinfo.scrape_time, "2000-01-01T00:00:00+00:00");
+
+    let before = modinfo.clone();
+    assert!(matches!(client.refresh(&mut modinfo), Err(crate::Error::NotFound)));
+    assert_eq!(modinfo, before);
+}
+
+#[test]
+fn mocked_get_raw_xml() {
+    let fixture = include_str!("../fixtures/view_by_moduleid.xml");
+    let url = mock_server(
+        vec![
+            http_response("200 OK", fixture),
+            http_response("200 OK", include_str!("../fixtures/not_found.xml"))
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url);
+    assert_eq!(client.get_raw_xml(61772).unwrap(), fixture);
+    assert!(matches!(client.get_raw_xml(30638), Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn mocked_get_clone_eq() {
+    let url = mock_server(
+        vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))]
+    );
+    let modinfo = Client::new("mock-key").with_base_url(url).get(61772).unwrap();
+    let mut cached = modinfo.clone();
+    assert_eq!(cached, modinfo);
+
+    cached.download_count += 1;
+    assert_ne!(cached, modinfo);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn mocked_get_async() {
+    let url = mock_server(
+        vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))]
+    );
+    let modinfo = crate::AsyncClient::new("mock-key").with_base_url(url).get(61772).await.unwrap();
+    assert_eq!(modinfo.filename, "7th_dance.xm");
+    assert_eq!(modinfo.title, "7th Dance");
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn mocked_async_error_status() {
+    let url = mock_server(vec![http_response("503 Service Unavailable", "")]);
+    let error = crate::AsyncClient::new("mock-key").with_base_url(url).get(61772).await.unwrap_err();
+    assert_eq!(error.http_status(), Some(503));
+}
+
+#[test]
+fn mocked_gzip() {
+    let body = include_str!("../fixtures/view_by_moduleid.xml");
+    let quota = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<modarchive><current>5</current><maximum>10</maximum></modarchive>";
+    let (url, requests) = mock_server_bytes(vec![gzip_response(body), gzip_response(quota)]);
+    let client = Client::new("mock-key").with_base_url(url);
+
+    let modinfo = client.get(61772).unwrap();
+    let expected = ModInfo { scrape_time: modinfo.scrape_time.clone(), ..ModInfo::from_xml(body, 61772).unwrap() };
+    assert_eq!(modinfo, expected);
+    assert!(requests.recv().unwrap().to_lowercase().contains("accept-encoding: gzip"));
+    assert_eq!(client.track_requests().unwrap(), "5 requests made out of 10");
+}
+
+#[test]
+fn mocked_cut_off_body() {
+    // the connection closes long before the promised 5000 bytes are there
+    let cut_off = "HTTP/1.1 200 OK\r\nContent-Length: 5000\r\nConnection: close\r\n\r\n<modarchive>";
+    let url = mock_server(vec![cut_off.to_string()]);
+    let client = Client::new("mock-key").with_base_url(url);
+    assert!(matches!(client.get_raw_xml(61772), Err(crate::Error::IOError(_))));
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn mocked_async_gzip() {
+    let body = include_str!("../fixtures/view_by_moduleid.xml");
+    let (url, requests) = mock_server_bytes(vec![gzip_response(body)]);
+    let modinfo = crate::AsyncClient::new("mock-key").with_base_url(url).get(61772).await.unwrap();
+    let expected = ModInfo { scrape_time: modinfo.scrape_time.clone(), ..ModInfo::from_xml(body, 61772).unwrap() };
+    assert_eq!(modinfo, expected);
+    assert!(requests.recv().unwrap().to_lowercase().contains("gzip"));
+}
+
+#[test]
+fn mod_id_zero() {
+    // the server is gone before the request, so getting anything but NotFound means one was made
+    let url = mock_server(vec![]);
+    let client = Client::new("mock-key").with_base_url(url);
+    assert!(matches!(client.get(0), Err(crate::Error::NotFound)));
+    assert!(matches!(client.get_raw_xml(0), Err(crate::Error::NotFound)));
+    assert!(matches!(client.try_get(0), Ok(None)));
+}
+
+#[test]
+fn invalid_api_key() {
+    // no server, the key is turned down before any request is made
+    assert!(matches!(ModInfo::get(61772, ""), Err(crate::Error::InvalidApiKey)));
+    assert!(matches!(ModInfo::track_requests(""), Err(crate::Error::InvalidApiKey)));
+    assert!(matches!(ModInfo::search_by_genre(3, 1, "not a key"), Err(crate::Error::InvalidApiKey)));
+
+    let search = ModSearch {
+        searchtype: "filename".into(),
+        searchquery: "noway".into(),
+        searchpage: None,
+        searchformat: None,
+        searchsize: None,
+        searchchannels: None,
+    };
+    assert!(matches!(search.search(""), Err(crate::Error::InvalidApiKey)));
+}
+
+#[test]
+fn key_redaction() {
+    assert_eq!(
+        crate::redact_key("https://modarchive.org/data/xml-tools.php?key=s3cr3t&request=search"),
+        "https://modarchive.org/data/xml-tools.php?key=***&request=search"
+    );
+    assert_eq!(crate::redact_key("http://localhost/?key=s3cr3t: status code 500"), "http://localhost/?key=***: status code 500");
+    assert_eq!(crate::redact_key("http://localhost/?monkey=banana"), "http://localhost/?monkey=banana");
+    assert_eq!(crate::redact_key("no URL here"), "no URL here");
+    assert_eq!(
+        crate::redact_key("query: Some(\"key=s3cr3t&request=view_by_moduleid\")"),
+        "query: Some(\"key=***&request=view_by_moduleid\")"
+    );
+    assert_eq!(crate::redact_key("key=s3cr3t"), "key=***");
+}
+
+/// Checks the key isn't anywhere in the error, including everything down its source chain
+fn assert_key_hidden(error: &crate::Error, key: &str) {
+    assert!(!format!("{:?}", error).contains(key));
+    let mut current: Option<&dyn std::error::Error> = Some(error);
+    while let Some(e) = current {
+        assert!(!e.to_string().contains(key));
+        assert!(!format!("{:?}", e).contains(key));
+        current = e.source();
+    }
+}
+
+#[test]
+fn error_key_redacted() {
+    let url = mock_server(vec![http_response("500 Internal Server Error", "")]);
+    let error = Client::new("s3cr3t").with_base_url(url).get(61772).unwrap_err();
+    assert_eq!(error.http_status(), Some(500));
+    assert_key_hidden(&error, "s3cr3t");
+    assert!(error.to_string().contains("key=***"));
+}
+
+/// A URL nothing is listening on, so connecting to it gets refused
+fn refused_url() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[test]
+fn error_key_redacted_connection_refused() {
+    let error = Client::new("s3cr3t").with_base_url(refused_url()).get(61772).unwrap_err();
+    assert!(matches!(error, crate::Error::APIRequestError(_)));
+    assert_key_hidden(&error, "s3cr3t");
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn async_error_key_redacted_connection_refused() {
+    let error = crate::AsyncClient::new("s3cr3t").with_base_url(refused_url()).get(61772).await.unwrap_err();
+    assert!(matches!(error, crate::Error::AsyncRequestError(_)));
+    assert_key_hidden(&error, "s3cr3t");
+}
+
+#[test]
+fn mocked_custom_agent() {
+    let url = mock_server(
+        vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))]
+    );
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(std::time::Duration::from_secs(5))
+        .redirects(0)
+        .build();
+    let modinfo = Client::new("mock-key").with_agent(agent).with_base_url(url).get(61772).unwrap();
+    assert_eq!(modinfo.filename, "7th_dance.xm");
+}
+
+#[test]
+fn mocked_proxy() {
+    let (proxy_url, requests) = mock_server_recording(vec![http_response("502 Bad Gateway", "")]);
+    let proxy = ureq::Proxy::new(proxy_url).unwrap();
+    let result = Client::new("mock-key")
+        .with_proxy(proxy)
+        .with_base_url("http://modarchive.invalid/data/xml-tools.php")
+        .get(61772);
+    assert!(result.is_err());
+    // a proxied request asks for the whole URL instead of just the path
+    let request = requests.recv().unwrap();
+    assert!(request.starts_with("GET http://modarchive.invalid/data/xml-tools.php?"));
+}
+
+#[test]
+fn mocked_proxy_keeps_agent_setup() {
+    let redirect = "HTTP/1.1 302 Found\r\nLocation: http://modarchive.invalid/cdn/7th_dance.xm\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    let (proxy_url, requests) = mock_server_recording(
+        vec![redirect.to_string(), http_response("200 OK", "Extended Module: 7th Dance")]
+    );
+    let client = Client::default()
+        .with_timeout(std::time::Duration::from_secs(5))
+        .with_proxy(ureq::Proxy::new(proxy_url).unwrap())
+        .with_download_url("http://modarchive.invalid/downloads.php");
+    // redirects are still followed through the proxy
+    assert_eq!(client.download_by_id(61772, "7th_dance.xm").unwrap(), b"Extended Module: 7th Dance");
+    assert!(requests.recv().unwrap().contains(concat!("modark/", env!("CARGO_PKG_VERSION"))));
+    assert!(requests.recv().unwrap().starts_with("GET http://modarchive.invalid/cdn/7th_dance.xm "));
+}
+
+#[test]
+fn request_timeout() {
+    // bound but never answered, so the request can only end by timing out
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let started = std::time::Instant::now();
+    let result = Client::new("mock-key")
+        .with_base_url(url)
+        .with_timeout(std::time::Duration::from_millis(200))
+        .get(61772);
+    assert!(matches!(result, Err(crate::Error::APIRequestError(_))));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
+
+#[test]
+fn retry_server_error() {
+    let url = mock_server(
+        vec![
+            http_response("503 Service Unavailable", ""),
+            http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))
+        ]
+    );
+    let modinfo = Client::new("mock-key").with_base_url(url).with_retries(2).get(61772).unwrap();
+    assert_eq!(modinfo.filename, "7th_dance.xm");
+}
+
+#[test]
+fn no_retry_not_found() {
+    // only a single response is served, so a retry would fail to connect instead
+    let url = mock_server(vec![http_response("200 OK", include_str!("../fixtures/not_found.xml"))]);
+    let result = Client::new("mock-key").with_base_url(url).with_retries(3).get(30638);
+    assert!(matches!(result, Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn mocked_get_many() {
+    let url = mock_server(
+        vec![
+            http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml")),
+            http_response("200 OK", include_str!("../fixtures/not_found.xml"))
+        ]
+    );
+    let results = Client::new("mock-key").with_base_url(url).get_many(&[61772, 30638]);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, 61772);
+    assert!(results[0].1.is_ok());
+    assert_eq!(results[1].0, 30638);
+    assert!(matches!(results[1].1, Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn mocked_exists() {
+    let url = mock_server(
+        vec![
+            http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml")),
+            http_response("200 OK", include_str!("../fixtures/not_found.xml")),
+            http_response("200 OK", include_str!("../fixtures/rate_limited.xml"))
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url);
+    assert!(client.exists(61772).unwrap());
+    assert!(!client.exists(30638).unwrap());
+    assert!(matches!(client.exists(61772), Err(crate::Error::RateLimited)));
+}
+
+#[test]
+fn mocked_get_list() {
+    let url = mock_server(
+        vec![
+            http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml")),
+            http_response("200 OK", include_str!("../fixtures/view_by_moduleid_bare.xml")),
+            http_response("200 OK", include_str!("../fixtures/not_found.xml"))
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url);
+    let modules = client.get_list(&[61772, 41070]).unwrap();
+    assert_eq!(modules.iter().map(|modinfo| modinfo.id).collect::<Vec<_>>(), [61772, 41070]);
+    assert!(matches!(client.get_list(&[30638]), Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn mocked_get_by_artist() {
+    let url = mock_server(
+        vec![http_response("200 OK", include_str!("../fixtures/view_modules_by_artistid.xml"))]
+    );
+    let results = Client::new("mock-key").with_base_url(url).get_by_artist_page(69141, 1).unwrap();
+    assert_eq!(results.total_pages, 2);
+    assert_eq!(results.total_results, 42);
+    assert_eq!(results.items.len(), 2);
+    assert_eq!(results.items[0].id, 61772);
+    assert_eq!(results.items[1].filename, "yrde_-_sunrise.it");
+    assert_eq!(results.items[1].title, "Sunrise");
+}
+
+#[test]
+fn mocked_get_by_artist_empty() {
+    let url = mock_server(
+        vec![
+            http_response(
+                "200 OK",
+                "<modarchive><results>0</results><totalpages>0</totalpages></modarchive>"
+            )
+        ]
+    );
+    let results = Client::new("mock-key").with_base_url(url).get_by_artist(1);
+    assert!(matches!(results, Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn mocked_search_by_genre() {
+    let url = mock_server(
+        vec![http_response("200 OK", include_str!("../fixtures/view_modules_by_artistid.xml"))]
+    );
+    let results = Client::new("mock-key").with_base_url(url).search_by_genre(3, 1).unwrap();
+    assert_eq!(results.len(), 2);
+}
+
+#[test]
+fn latest_additions() {
+    let additions = ModInfo::latest_additions(1).unwrap();
+    assert!(!additions.is_empty());
+    assert!(additions.windows(2).all(|pair| pair[0].id > pair[1].id));
+}
+
+#[test]
+fn fixture_latest_additions() {
+    let body = include_str!("../fixtures/latest_additions.html");
+    let additions = crate::search::parse_latest_additions(body, 1).unwrap();
+    // newest first, whatever order the page has them in
+    assert_eq!(additions.iter().map(|module| module.id).collect::<Vec<_>>(), [88676, 61801, 61772]);
+    assert_eq!(additions[1].filename, "rocknroll.it");
+    assert_eq!(additions[1].title, "rock & roll");
+
+    let error_page = "<html><body><h1 class=\"site-wide-page-head-title\">Error</h1></body></html>";
+    assert!(matches!(crate::search::parse_latest_additions(error_page, 1), Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn charts() {
+    assert!(!ModInfo::top_downloads(1).unwrap().is_empty());
+    assert!(!ModInfo::top_favourites(1).unwrap().is_empty());
+}
+
+#[test]
+fn fixture_chart() {
+    let chart = crate::search::parse_chart(include_str!("../fixtures/chart_tophits.html"), 1).unwrap();
+    // ranked order, not sorted by ID
+    assert_eq!(chart.iter().map(|module| module.id).collect::<Vec<_>>(), [50051, 88676, 61772]);
+    assert_eq!(chart[0].filename, "noway.s3m");
+    assert_eq!(chart[0].title, "no way & back");
+}
+
+#[test]
+fn genre_round_trip() {
+    use crate::{ ElectronicGenre, Genre, MetalGenre };
+
+    let ambient = Genre::from_text("Electronic - Ambient");
+    assert_eq!(ambient, Genre::Electronic(ElectronicGenre::Ambient));
+    assert_eq!(ambient.to_string(), "Electronic - Ambient");
+    assert_eq!(ambient.id(), Some(2));
+
+    let metal = Genre::from_text("Metal (general)");
+    assert_eq!(metal, Genre::Metal(MetalGenre::General));
+    assert_eq!(metal.to_string(), "Metal (general)");
+
+    let unknown = Genre::from_text("Polka - Extreme");
+    assert_eq!(unknown, Genre::Other("Polka - Extreme".into()));
+    assert_eq!(unknown.id(), None);
+
+    // every genre in the list should parse into something known and back again
+    for (id, text) in crate::GENRES {
+        let genre = Genre::from_text(text);
+        assert!(!matches!(genre, Genre::Other(_)));
+        assert_eq!(genre.to_string(), *text);
+        assert_eq!(genre.id(), Some(*id));
+    }
+}
+
+#[test]
+fn genre_ids_unique() {
+    let mut ids: Vec<u32> = crate::GENRES.iter().map(|(id, _)| *id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), crate::GENRES.len());
+}
+
+#[test]
+fn error_http_status() {
+    let url = mock_server(vec![http_response("429 Too Many Requests", "")]);
+    let error = Client::new("mock-key").with_base_url(url).get(61772).unwrap_err();
+    assert_eq!(error.http_status(), Some(429));
+
+    // nothing listens on port 9 here, so this never gets as far as a status code
+    let error = Client::new("mock-key").with_base_url("http://127.0.0.1:9").get(61772).unwrap_err();
+    assert_eq!(error.http_status(), None);
+}
+
+#[test]
+fn quota_guard() {
+    let quota = |current: u32| format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<modarchive><current>{current}</current><maximum>10</maximum></modarchive>"
+    );
+    let (url, requests) = mock_server_recording(
+        vec![
+            http_response("200 OK", &quota(9)),
+            http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url).with_quota_guard(crate::QuotaGuard::Error);
+
+    assert!(client.get(61772).is_ok());
+    assert!(requests.recv().unwrap().contains("&request=view_requests "));
+    assert!(requests.recv().unwrap().contains("&request=view_by_moduleid"));
+
+    // the one request left got used up, so the next one isn't even sent (clones share the count)
+    assert!(matches!(client.clone().get(61772), Err(crate::Error::RateLimited)));
+    assert!(requests.try_recv().is_err());
+}
+
+#[test]
+fn quota_guard_unlocked_while_checking() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let client = Client::new("mock-key").with_base_url(url).with_quota_guard(crate::QuotaGuard::Error);
+    let accept = || {
+        let started = std::time::Instant::now();
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false).unwrap();
+                    return stream;
+                }
+                Err(_) if started.elapsed() < std::time::Duration::from_secs(5) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                Err(e) => panic!("the quota was never asked for: {e}"),
+            }
+        }
+    };
+
+    // the first check is stuck waiting on its quota request, which mustn't hold up the second
+    let first = {
+        let client = client.clone();
+        std::thread::spawn(move || client._check_quota())
+    };
+    let first_stream = accept();
+    let second = {
+        let client = client.clone();
+        std::thread::spawn(move || client._check_quota())
+    };
+    let second_stream = accept();
+
+    let quota = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<modarchive><current>5</current><maximum>10</maximum></modarchive>";
+    for mut stream in [first_stream, second_stream] {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        stream.write_all(http_response("200 OK", quota).as_bytes()).unwrap();
+    }
+    assert!(first.join().unwrap().is_ok());
+    assert!(second.join().unwrap().is_ok());
+}
+
+#[test]
+fn cached_client() {
+    let body = include_str!("../fixtures/view_by_moduleid.xml");
+    let (url, requests) = mock_server_recording(
+        vec![
+            http_response("200 OK", body),
+            http_response("200 OK", body),
+            http_response("200 OK", body),
+            http_response("200 OK", body)
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url);
+    let cache = crate::CachedClient::new(client.clone(), std::time::Duration::from_secs(3600));
+
+    let first = cache.get(61772).unwrap();
+    assert_eq!(cache.get(61772).unwrap(), first);
+    requests.recv().unwrap();
+    assert!(requests.try_recv().is_err());
+
+    cache.invalidate(61772);
+    cache.get(61772).unwrap();
+    requests.recv().unwrap();
+
+    // nothing stays cached with no TTL
+    let uncached = crate::CachedClient::new(client, std::time::Duration::ZERO);
+    uncached.get(61772).unwrap();
+    uncached.get(61772).unwrap();
+    requests.recv().unwrap();
+    requests.recv().unwrap();
+
+    // the cache can be shared between threads
+    let cache = std::sync::Arc::new(cache);
+    let shared = std::sync::Arc::clone(&cache);
+    std::thread::spawn(move || shared.clear()).join().unwrap();
+}
+
+#[test]
+fn error_summary() {
+    let url = mock_server(vec![http_response("429 Too Many Requests", "")]);
+    let error = Client::new("s3cr3t").with_base_url(url).get(61772).unwrap_err();
+    let summary = error.summary();
+    assert_eq!(summary.kind, crate::ErrorKind::Request);
+    assert_eq!(summary.http_status, Some(429));
+    assert_eq!(summary.to_string(), error.to_string());
+    assert!(!summary.message.contains("s3cr3t"));
+
+    let summaries = [summary.clone(), crate::Error::NotFound.summary()];
+    assert_eq!(summaries[0], summary);
+    assert_eq!(summaries[1].kind, crate::ErrorKind::NotFound);
+    assert_eq!(summaries[1].http_status, None);
+}
+
+#[test]
+fn maintenance_page() {
+    let page = include_str!("../fixtures/maintenance.html");
+    assert!(matches!(ModInfo::from_xml(page, 61772), Err(crate::Error::ServiceUnavailable)));
+    assert!(matches!(crate::search::parse_module_list(page), Err(crate::Error::ServiceUnavailable)));
+
+    let url = mock_server(vec![http_response("200 OK", page)]);
+    let error = Client::new("mock-key").with_base_url(url).get(61772).unwrap_err();
+    assert!(matches!(error, crate::Error::ServiceUnavailable));
+    assert_eq!(error.kind(), crate::ErrorKind::ServiceUnavailable);
+
+    // broken XML is still a parse error
+    assert!(matches!(ModInfo::from_xml("<modarchive><module>", 61772), Err(crate::Error::XMLParsingError(_))));
+}
+
+#[test]
+fn fixture_rate_limited() {
+    let limited = ModInfo::from_xml(include_str!("../fixtures/rate_limited.xml"), 61772);
+    assert!(matches!(limited, Err(crate::Error::RateLimited)));
+
+    let limited = crate::search::parse_module_list(include_str!("../fixtures/rate_limited.xml"));
+    assert!(matches!(limited, Err(crate::Error::RateLimited)));
+}
+
+#[test]
+fn user_agent_sent() {
+    let (url, requests) = mock_server_recording(
+        vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))]
+    );
+    Client::new("mock-key").with_base_url(url).get(61772).unwrap();
+
+    let request = requests.recv().unwrap().to_lowercase();
+    let expected = format!("user-agent: modark/{}", env!("CARGO_PKG_VERSION"));
+    assert!(request.contains(&expected));
+}
+
+#[test]
+fn mocked_get_by_hash() {
+    let (url, requests) = mock_server_recording(
+        vec![http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))]
+    );
+    let modinfo = Client::new("mock-key")
+        .with_base_url(url)
+        .get_by_hash("5A6B1E4D0B2C9F8E7D6C5B4A39281706")
+        .unwrap();
+    assert_eq!(modinfo.id, 61772);
+    assert_eq!(modinfo.filename, "7th_dance.xm");
+
+    let request = requests.recv().unwrap();
+    assert!(request.contains("request=search&type=hash&query=5a6b1e4d0b2c9f8e7d6c5b4a39281706"));
+}
+
+#[test]
+fn mocked_get_by_hash_not_found() {
+    let url = mock_server(
+        vec![http_response("200 OK", "<?xml version=\"1.0\"?><modarchive><results>0</results></modarchive>")]
+    );
+    let error = Client::new("mock-key")
+        .with_base_url(url)
+        .get_by_hash("5a6b1e4d0b2c9f8e7d6c5b4a39281706")
+        .unwrap_err();
+    assert!(matches!(error, crate::Error::NotFound));
+
+    // only the elements count, not text that happens to look like one
+    let url = mock_server(
+        vec![
+            http_response(
+                "200 OK",
+                "<?xml version=\"1.0\"?><modarchive><results>0</results><note><![CDATA[no <module> found]]></note></modarchive>"
+            ),
+            http_response("200 OK", include_str!("../fixtures/rate_limited.xml")),
+            http_response(
+                "200 OK",
+                &include_str!("../fixtures/view_by_moduleid.xml").replacen("<module>", "<module  >", 1)
+            )
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url);
+    let md5 = "5a6b1e4d0b2c9f8e7d6c5b4a39281706";
+    assert!(matches!(client.get_by_hash(md5), Err(crate::Error::NotFound)));
+    assert!(matches!(client.get_by_hash(md5), Err(crate::Error::RateLimited)));
+    assert_eq!(client.get_by_hash(md5).unwrap().id, 61772);
+}
+
+#[test]
+fn get_by_hash_invalid() {
+    // no server, the hash is turned down before any request is made
+    for md5 in ["", "5a6b1e4d", "zz6b1e4d0b2c9f8e7d6c5b4a39281706"] {
+        let error = Client::new("mock-key").get_by_hash(md5).unwrap_err();
+        assert!(matches!(error, crate::Error::InvalidHash(_)));
+    }
+}
+
+#[test]
+fn invalid_modid() {
+    let invalid = ModInfo::get(
+        30638,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    );
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn valid_modid() {
+    let valid = ModInfo::get(
+        99356,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    );
+    assert!(valid.is_ok());
+}
+
+#[test]
+fn try_get_missing() {
+    let key = env
+        ::var("MODARCH_KEY")
+        .expect("Expected a Mod Archive API key in the environment variables");
+    assert!(ModInfo::try_get(30638, &key).unwrap().is_none());
+    assert!(ModInfo::try_get(99356, &key).unwrap().is_some());
+}
+
+#[test]
+fn size_in_bytes() {
+    let module = ModInfo::get(
+        41070,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    ).unwrap();
+    assert!(module.size_bytes > 0);
+}
+
+#[test]
+fn upload_date() {
+    let module = ModInfo::get(
+        41070,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    ).unwrap();
+    assert!(module.upload_date_parsed().is_some());
+}
+
+#[test]
+fn upload_date_formats() {
+    let parsed = crate::parse_modarchive_date("Mon 5th Jul 2004").unwrap();
+    assert_eq!(parsed.to_rfc3339(), "2004-07-05T00:00:00+00:00");
+
+    let parsed = crate::parse_modarchive_date("Sun 21st Nov 2010").unwrap();
+    assert_eq!(parsed.to_rfc3339(), "2010-11-21T00:00:00+00:00");
+
+    assert!(crate::parse_modarchive_date("sometime last week").is_none());
+}
+
+#[test]
+fn format_parsing() {
+    use crate::Format;
+
+    assert_eq!(Format::from("XM"), Format::Xm);
+    assert_eq!(Format::from("s3m"), Format::S3m);
+    assert_eq!(Format::from("669"), Format::SixSixNine);
+    assert_eq!(Format::from("DBM"), Format::Other("DBM".into()));
+    assert_eq!(Format::It.to_string(), "IT");
+}
 
 #[test]
-fn instr_text() {
-    let instr_text = ModInfo::get(
-        61772,
-        &env
-            ::var("MODARCH_KEY")
-            .expect("Expected a Mod Archive API key in the environment variables")
-    ).unwrap().instrument_text;
-    assert_eq!(
-        instr_text,
-        "\n        7th  Dance\n\n             By:\n Jari Ylamaki aka Yrde\n  27.11.2000 HELSINKI\n\n            Finland\n           SITE :\n  www.mp3.com/Yrde"
+fn known_formats_in_sync() {
+    use crate::Format;
+
+    let formats = ModInfo::known_formats();
+    assert!(formats.contains(&Format::Mod) && formats.contains(&Format::Hvl));
+    for format in formats {
+        assert_eq!(&Format::from(format.to_string().as_str()), format);
+    }
+    // no duplicates
+    let names: std::collections::HashSet<_> = formats.iter().map(|format| format.to_string()).collect();
+    assert_eq!(names.len(), formats.len());
+}
+
+#[test]
+fn request_quota_parsing() {
+    let body =
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<modarchive>
+    <current>5</current>
+    <maximum>10000</maximum>
+</modarchive>"#;
+    let quota = crate::RequestQuota::_parse(body).unwrap();
+    assert_eq!(quota.current, 5);
+    assert_eq!(quota.maximum, 10000);
+
+    let malformed = [
+        "<modarchive/>",
+        "<modarchive><current>5</current></modarchive>",
+        "<modarchive><current>five</current><maximum>10</maximum></modarchive>",
+    ];
+    for body in malformed {
+        assert!(matches!(crate::RequestQuota::_parse(body), Err(crate::Error::MalformedResponse)));
+    }
+}
+
+#[test]
+fn quota_guard_malformed_quota() {
+    let quota = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<modarchive><current>5</current><maximum>10</maximum></modarchive>";
+    let (url, requests) = mock_server_recording(
+        vec![
+            http_response("200 OK", "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<modarchive/>"),
+            http_response("200 OK", quota),
+            http_response("200 OK", include_str!("../fixtures/view_by_moduleid.xml"))
+        ]
     );
+    let client = Client::new("mock-key").with_base_url(url).with_quota_guard(crate::QuotaGuard::Error);
+
+    // an empty quota isn't taken as no requests left, the request just isn't sent
+    assert!(matches!(client.get(61772), Err(crate::Error::MalformedResponse)));
+    assert!(requests.recv().unwrap().contains("&request=view_requests "));
+    assert!(requests.try_recv().is_err());
+
+    // nothing got remembered, so the quota is asked for again
+    assert!(client.get(61772).is_ok());
+    assert!(requests.recv().unwrap().contains("&request=view_requests "));
+    assert!(requests.recv().unwrap().contains("&request=view_by_moduleid"));
 }
 
 #[test]
-fn invalid_modid() {
-    let invalid = ModInfo::get(
-        30638,
+fn mocked_track_requests() {
+    let url = mock_server(
+        vec![
+            http_response("200 OK", "<modarchive><current>5</current><maximum>10000</maximum></modarchive>"),
+            http_response("200 OK", include_str!("../fixtures/invalid_key.xml"))
+        ]
+    );
+    let client = Client::new("mock-key").with_base_url(url);
+    assert_eq!(client.track_requests().unwrap(), "5 requests made out of 10000");
+    assert!(matches!(client.track_requests(), Err(crate::Error::InvalidApiKey)));
+}
+
+#[test]
+fn track_requests_unreachable() {
+    // bound then dropped, so nothing is listening there anymore
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let result = Client::new("mock-key").with_base_url(url).track_requests();
+    assert!(matches!(result, Err(crate::Error::APIRequestError(_))));
+}
+
+#[test]
+fn spotlit_modid() {
+    let module = ModInfo::get(
+        158263,
         &env
             ::var("MODARCH_KEY")
             .expect("Expected a Mod Archive API key in the environment variables")
-    );
-    assert!(invalid.is_err());
+    ).unwrap();
+    assert!(module.spotlit);
 }
 
 #[test]
-fn valid_modid() {
-    let valid = ModInfo::get(
-        99356,
+fn random_module() {
+    let random = ModInfo::get_random(
         &env
             ::var("MODARCH_KEY")
             .expect("Expected a Mod Archive API key in the environment variables")
-    );
-    assert!(valid.is_ok());
+    ).unwrap();
+    assert_ne!(random.id, 0);
 }
 
-/*
 #[test]
-fn spotlit_modid() {
-    let module = ModInfo::get(158263, &env::var("MODARCH_KEY").expect("Expected a Mod Archive API key in the environment variables")).unwrap();
-    assert!(module.spotlit);
+fn format_filtered_search() {
+    let search = ModSearch {
+        searchtype: "filename".into(),
+        searchquery: "noway".into(),
+        searchpage: None,
+        searchformat: Some("S3M".into()),
+        searchsize: None,
+        searchchannels: None,
+    };
+    let results = search
+        .search(
+            &env
+                ::var("MODARCH_KEY")
+                .expect("Expected a Mod Archive API key in the environment variables")
+        )
+        .unwrap()
+        .items;
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|result| result.filename.to_lowercase().ends_with(".s3m")));
+}
+
+#[test]
+fn module_list_parsing() {
+    let body =
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<modarchive>
+    <results>2</results>
+    <totalpages>1</totalpages>
+    <module>
+        <filename>noway.s3m</filename>
+        <format>S3M</format>
+        <id>50051</id>
+        <artist_info><artist><id>69141</id></artist></artist_info>
+    </module>
+    <module>
+        <filename>noway2.s3m</filename>
+        <format>S3M</format>
+        <id>50052</id>
+    </module>
+</modarchive>"#;
+    let results = crate::search::parse_module_list(body).unwrap();
+    assert_eq!(results.total_results, 2);
+    let results = results.items;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].id, 50051);
+    assert_eq!(results[0].filename, "noway.s3m");
+    assert_eq!(results[1].id, 50052);
 }
-*/
 
 #[test]
 fn name_resolving() {
@@ -57,6 +1271,686 @@ fn name_resolving() {
     );
 }
 
+#[test]
+fn title_resolving() {
+    let results = ModInfo::resolve("virtual monotone", crate::SearchType::Title).unwrap();
+    assert!(results.iter().any(|result| result.id == 88676));
+}
+
+#[test]
+fn name_resolving_pages() {
+    let first_page = ModInfo::resolve_filename_page("dance", 1).unwrap();
+    assert!(first_page.total_pages > 1);
+
+    let second_page = ModInfo::resolve_filename_page("dance", 2).unwrap();
+    assert!(!second_page.items.is_empty());
+    assert_ne!(first_page.items[0].id, second_page.items[0].id);
+}
+
+#[test]
+fn resolve_all() {
+    let results: Vec<_> = ModInfo::resolve_all("dance", crate::SearchType::Filename)
+        .take(50)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(results.len(), 50);
+}
+
+#[test]
+fn resolve_all_pages() {
+    let fetched = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let pages = fetched.clone();
+    let results: Vec<u32> = crate::ResolveAll::_new(move |page| {
+        pages.borrow_mut().push(page);
+        // two full pages and a partial last one
+        let items = (0..if page == 3 { 1 } else { 2 })
+            .map(|i| crate::ModSearchResolve {
+                id: page * 10 + i,
+                filename: String::new(),
+                title: String::new(),
+            })
+            .collect();
+        Ok(crate::SearchResults { items, total_results: 5, total_pages: 3 })
+    })
+        .map(|result| result.unwrap().id)
+        .collect();
+    assert_eq!(results, [10, 11, 20, 21, 30]);
+    assert_eq!(*fetched.borrow(), [1, 2, 3]);
+}
+
+#[test]
+fn resolve_all_lazy() {
+    let fetched = std::rc::Rc::new(std::cell::RefCell::new(0));
+    let pages = fetched.clone();
+    let mut results = crate::ResolveAll::_new(move |page| {
+        *pages.borrow_mut() += 1;
+        let items = vec![crate::ModSearchResolve {
+            id: page,
+            filename: String::new(),
+            title: String::new(),
+        }];
+        Ok(crate::SearchResults { items, total_results: 3, total_pages: 3 })
+    });
+    assert_eq!(results.next().unwrap().unwrap().id, 1);
+    assert_eq!(*fetched.borrow(), 1);
+}
+
+#[test]
+fn resolve_all_stops_on_error() {
+    let mut results = crate::ResolveAll::_new(|_| Err(crate::Error::RateLimited));
+    assert!(matches!(results.next(), Some(Err(crate::Error::RateLimited))));
+    assert!(results.next().is_none());
+}
+
+#[test]
+fn modref_parsing() {
+    use crate::ModRef;
+    assert_eq!("51772".parse::<ModRef>().unwrap(), ModRef::Id(51772));
+    assert_eq!(" 51772 ".parse::<ModRef>().unwrap(), ModRef::Id(51772));
+    assert_eq!("noway.s3m".parse::<ModRef>().unwrap(), ModRef::Filename("noway.s3m".into()));
+    assert_eq!("2nd_reality.s3m".parse::<ModRef>().unwrap(), ModRef::Filename("2nd_reality.s3m".into()));
+    // too big to be an ID
+    assert_eq!("99999999999".parse::<ModRef>().unwrap(), ModRef::Filename("99999999999".into()));
+}
+
+#[test]
+fn exact_filename_matches() {
+    let results = ["noway.s3m", "NOWAY.S3M", "noway2.s3m"]
+        .iter()
+        .enumerate()
+        .map(|(id, filename)| crate::ModSearchResolve {
+            id: id as u32,
+            filename: filename.to_string(),
+            title: String::new(),
+        })
+        .collect::<Vec<_>>();
+
+    let exact = crate::exact_matches(results.clone(), "noway.s3m", false);
+    assert_eq!(exact.iter().map(|result| result.id).collect::<Vec<_>>(), [0]);
+
+    let exact = crate::exact_matches(results, "noway.s3m", true);
+    assert_eq!(exact.iter().map(|result| result.id).collect::<Vec<_>>(), [0, 1]);
+}
+
+#[test]
+fn best_search_match() {
+    let results = ["noway2.s3m", "NOWAY.S3M", "noway.s3m"]
+        .iter()
+        .enumerate()
+        .map(|(id, filename)| crate::ModSearchResolve {
+            id: id as u32,
+            filename: filename.to_string(),
+            title: String::new(),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(crate::best_match(&results, "noway.s3m").map(|result| result.id), Some(1));
+    assert_eq!(crate::best_match(&results, "nothing.xm").map(|result| result.id), Some(0));
+    assert_eq!(crate::best_match(&[], "noway.s3m"), None);
+}
+
+#[test]
+fn sort_search_results() {
+    let mut results = [(3, "b.xm", "Zebra"), (1, "C.it", "apple"), (2, "a.mod", "Mango"), (0, "b.xm", "")]
+        .iter()
+        .map(|(id, filename, title)| crate::ModSearchResolve {
+            id: *id,
+            filename: filename.to_string(),
+            title: title.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let ids = |results: &[crate::ModSearchResolve]| results.iter().map(|result| result.id).collect::<Vec<_>>();
+
+    crate::ModSearchResolve::sort_results(&mut results, crate::SortKey::Id);
+    assert_eq!(ids(&results), [0, 1, 2, 3]);
+
+    // the two b.xm are tied, so their IDs decide
+    crate::ModSearchResolve::sort_results(&mut results, crate::SortKey::Filename);
+    assert_eq!(ids(&results), [2, 0, 3, 1]);
+
+    let mut page = crate::SearchResults { items: results, total_results: 4, total_pages: 1 };
+    page.sort(crate::SortKey::Title);
+    assert_eq!(ids(&page.items), [0, 1, 2, 3]);
+}
+
+#[test]
+fn search_result_identity() {
+    let result = |id: u32, filename: &str| crate::ModSearchResolve {
+        id,
+        filename: filename.into(),
+        title: String::new(),
+    };
+    // the same module showing up on two pages, once with a different filename casing
+    let results: std::collections::HashSet<_> = [result(2, "noway.s3m"), result(1, "a.mod"), result(2, "NOWAY.S3M")]
+        .into_iter()
+        .collect();
+    assert_eq!(results.len(), 2);
+
+    let mut sorted = [result(3, "a.mod"), result(1, "c.mod"), result(2, "b.mod")];
+    sorted.sort();
+    assert_eq!(sorted.iter().map(|result| result.id).collect::<Vec<_>>(), [1, 2, 3]);
+    assert!(result(1, "z.mod") < result(2, "a.mod"));
+}
+
+#[test]
+fn modinfo_by_id_identity() {
+    use crate::ModInfoById;
+
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    // fetched again later, the numbers changed but it's still the same module
+    let refetched = ModInfo { download_count: modinfo.download_count + 1, ..modinfo.clone() };
+    let other = ModInfo { id: 50051, ..modinfo.clone() };
+    assert_ne!(modinfo, refetched);
+
+    let modules: std::collections::HashSet<ModInfoById> = [modinfo.clone(), refetched, other.clone()]
+        .into_iter()
+        .map(ModInfoById::from)
+        .collect();
+    assert_eq!(modules.len(), 2);
+
+    let mut sorted = [ModInfoById(modinfo), ModInfoById(other)];
+    sorted.sort();
+    assert_eq!(sorted.iter().map(|module| module.id).collect::<Vec<_>>(), [50051, 61772]);
+}
+
+#[test]
+fn search_result_extension_format() {
+    let result = |filename: &str| crate::ModSearchResolve {
+        id: 1,
+        filename: filename.into(),
+        title: String::new(),
+    };
+    assert_eq!(result("noway.s3m").extension_format(), crate::Format::S3m);
+    assert_eq!(result("7TH_DANCE.XM").extension_format(), crate::Format::Xm);
+    assert_eq!(result("tune.669").extension_format(), crate::Format::SixSixNine);
+    assert_eq!(result("readme").extension_format(), crate::Format::Other(String::new()));
+}
+
+#[test]
+fn search_ranges() {
+    let search = ModSearch {
+        searchtype: "filename".into(),
+        searchquery: "noway".into(),
+        searchpage: None,
+        searchformat: None,
+        searchsize: None,
+        searchchannels: None,
+    };
+    assert_eq!(search.clone().channels_min(4).searchchannels.as_deref(), Some("4-"));
+
+    let search = search.channels_range(4, 8).size_range(0, 500);
+    assert_eq!(search.searchchannels.as_deref(), Some("4-8"));
+    assert_eq!(search.searchsize.as_deref(), Some("0-500"));
+}
+
+#[test]
+fn search_default() {
+    let search = ModSearch { searchquery: "noway".into(), ..Default::default() };
+    assert_eq!(search.searchtype, "filename");
+    assert_eq!(search._params(), [("type", "filename".to_string()), ("query", "noway".to_string())]);
+}
+
+#[test]
+#[should_panic(expected = "bigger than the maximum")]
+fn search_range_backwards() {
+    let search = ModSearch {
+        searchtype: "filename".into(),
+        searchquery: "noway".into(),
+        searchpage: None,
+        searchformat: None,
+        searchsize: None,
+        searchchannels: None,
+    };
+    search.channels_range(8, 4);
+}
+
+#[test]
+fn search_page_url_encoded() {
+    assert_eq!(
+        crate::search_page_url("my song&x#1.mod", crate::SearchType::Filename, 2),
+        "https://modarchive.org/index.php?request=search&query=my%20song%26x%231.mod&submit=Find&search_type=filename&page=2"
+    );
+}
+
+#[test]
+fn mocked_search_encoded() {
+    let (url, requests) = mock_server_recording(
+        vec![http_response("200 OK", include_str!("../fixtures/view_modules_by_artistid.xml"))]
+    );
+    let search = ModSearch {
+        searchtype: "filename".into(),
+        searchquery: "my song&type=hash#1".into(),
+        searchpage: None,
+        searchformat: None,
+        searchsize: None,
+        searchchannels: None,
+    };
+    Client::new("mock-key").with_base_url(url).search(&search).unwrap();
+
+    let request = requests.recv().unwrap();
+    assert!(request.contains("&type=filename&query=my%20song%26type%3Dhash%231 "));
+}
+
+#[test]
+fn module_page_tags() {
+    let body =
+        r#"<html><body>
+<h1>7th Dance</h1>
+<a href="https://modarchive.org/index.php?request=view_profile&query=69141">Yrde</a>
+<a href="https://modarchive.org/index.php?request=search&query=trance&search_type=tags"> trance </a>
+<a href="https://modarchive.org/index.php?request=search&query=melodic&search_type=tags">melodic</a>
+<a href="https://modarchive.org/index.php?request=search&query=trance&search_type=tags">trance</a>
+</body></html>"#;
+    assert_eq!(ModInfo::_parse_module_page(body).unwrap(), ["trance", "melodic"]);
+    assert!(ModInfo::_parse_module_page("<html></html>").unwrap().is_empty());
+}
+
+#[test]
+fn scraped_module_page() {
+    let modinfo = ModInfo::_parse_scraped_page(include_str!("../fixtures/module_page.html"), 61772).unwrap();
+    let from_xml = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(modinfo.filename, from_xml.filename);
+    assert_eq!(modinfo.title, from_xml.title);
+    assert_eq!(modinfo.md5, from_xml.md5);
+    assert_eq!(modinfo.format, "XM");
+    assert_eq!(modinfo.download_count, 3021);
+    assert_eq!(modinfo.fav_count, 5);
+    assert_eq!(modinfo.channel_count, 16);
+    assert_eq!(modinfo.genre, from_xml.genre);
+    assert_eq!(modinfo.tags, ["trance"]);
+    // the page doesn't have these
+    assert!(modinfo.instrument_text.is_empty());
+    assert_eq!(modinfo.artist, None);
+
+    let error_page = r#"<html><body><h1 class="site-wide-page-head-title">Error</h1></body></html>"#;
+    assert!(matches!(ModInfo::_parse_scraped_page(error_page, 61772), Err(crate::Error::NotFound)));
+    assert!(matches!(ModInfo::_parse_scraped_page("<html></html>", 61772), Err(crate::Error::ScrapeError(_))));
+
+    // the info is there but the download link isn't, so the page layout likely changed
+    let no_link = include_str!("../fixtures/module_page.html").replace("downloads.php", "elsewhere.php");
+    assert!(matches!(ModInfo::_parse_scraped_page(&no_link, 61772), Err(crate::Error::ScrapeError(_))));
+}
+
+#[test]
+fn scraped_module_page_error_title() {
+    // "error" in the title or filename doesn't make it an error page
+    let modinfo = ModInfo::_parse_scraped_page(include_str!("../fixtures/module_page_error_title.html"), 61773)
+        .unwrap();
+    assert_eq!(modinfo.title, "Terror");
+    assert_eq!(modinfo.filename, "error.mod");
+    assert_eq!(modinfo.format, "MOD");
+}
+
+#[test]
+fn search_page_parsing() {
+    let body =
+        r#"<html><body>
+<h1 class="site-wide-page-head-title">Search Results</h1>
+<p>Showing 1 - 40 of 1,312 results</p>
+<a class="standard-link" title="virtual monotone" href="https://modarchive.org/index.php?request=view_by_moduleid&query=88676">virtual-monotone.mod</a>
+<a class="standard-link" title="no way &amp; back" href="https://modarchive.org/index.php?request=view_by_moduleid&query=50051">noway.s3m</a>
+<a href="https://modarchive.org/index.php?request=search&query=mono&search_type=filename&page=2">2</a>
+<a href="https://modarchive.org/index.php?request=search&query=mono&search_type=filename&page=3">3</a>
+</body></html>"#;
+    let results = ModInfo::_parse_search_page(body, 1).unwrap();
+    assert_eq!(results.items.len(), 2);
+    assert_eq!(results.items[0].id, 88676);
+    assert_eq!(results.items[0].filename, "virtual-monotone.mod");
+    assert_eq!(results.items[0].title, "virtual monotone");
+    assert_eq!(results.items[1].title, "no way & back");
+    assert_eq!(results.total_pages, 3);
+    assert_eq!(results.total_results, 1312);
+}
+
+#[test]
+fn search_page_without_count() {
+    let body =
+        r#"<html><body>
+<h1 class="site-wide-page-head-title">Search Results</h1>
+<a class="standard-link" title="noway" href="https://modarchive.org/index.php?request=view_by_moduleid&query=50051">noway.s3m</a>
+</body></html>"#;
+    let results = ModInfo::_parse_search_page(body, 1).unwrap();
+    assert_eq!(results.total_results, 1);
+    assert_eq!(results.total_pages, 1);
+}
+
+#[test]
+fn result_count_text() {
+    assert_eq!(crate::parse_result_count("Showing 1 - 40 of 312 results"), Some(312));
+    assert_eq!(crate::parse_result_count("Results 1-40 of 312"), Some(312));
+    assert_eq!(crate::parse_result_count("No modules here"), None);
+}
+
+#[test]
+fn search_page_bad_link() {
+    let body =
+        r#"<html><body>
+<h1 class="site-wide-page-head-title">Search Results</h1>
+<a class="standard-link" title="broken" href="https://modarchive.org/">broken.mod</a>
+</body></html>"#;
+    assert!(ModInfo::_parse_search_page(body, 1).is_err());
+    match ModInfo::_parse_search_page(body, 1) {
+        Err(crate::Error::ScrapeError(message)) => assert!(message.contains("https://modarchive.org/")),
+        result => panic!("expected a scrape error, got {:?}", result),
+    }
+}
+
+#[test]
+fn search_page_no_results() {
+    let body =
+        r#"<html><body>
+<h1 class="site-wide-page-head-title">Search Results</h1>
+<p>No modules matched your search</p>
+</body></html>"#;
+    let results = ModInfo::_parse_search_page(body, 1).unwrap();
+    assert!(results.items.is_empty());
+    assert_eq!(results.total_results, 0);
+    assert_eq!(results.total_pages, 1);
+}
+
+#[test]
+fn search_page_error() {
+    let body = r#"<html><body><h1 class="site-wide-page-head-title">Error: Page Not Found</h1></body></html>"#;
+    assert!(matches!(ModInfo::_parse_search_page(body, 1), Err(crate::Error::NotFound)));
+}
+
+#[test]
+fn search_page_redesigned() {
+    let body = r#"<html><body><h1 class="page-title">Search Results</h1></body></html>"#;
+    match ModInfo::_parse_search_page(body, 1) {
+        Err(crate::Error::ScrapeError(message)) => assert!(message.contains("h1.site-wide-page-head-title")),
+        result => panic!("expected a scrape error, got {:?}", result),
+    }
+}
+
+#[test]
+fn mocked_download() {
+    let (url, requests) = mock_server_recording(
+        vec![http_response("200 OK", "module data"), http_response("200 OK", "module data")]
+    );
+    let client = Client::default().with_download_url(format!("{}/mirror.php", url));
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    assert_eq!(client.download_link(61772, "7th_dance.xm"), format!("{}/mirror.php?moduleid=61772#7th_dance.xm", url));
+
+    let mut last_progress = None;
+    let bytes = client.download_with_progress(&modinfo, |read, total| {
+        last_progress = Some((read, total));
+    }).unwrap();
+    assert_eq!(bytes, b"module data");
+    assert_eq!(last_progress, Some((11, Some(11))));
+    assert!(requests.recv().unwrap().starts_with("GET /mirror.php?moduleid=61772 "));
+
+    // the fixture's hash is not the hash of "module data"
+    assert!(matches!(client.download_verified(&modinfo), Err(crate::Error::ChecksumMismatch { .. })));
+}
+
+#[test]
+fn mocked_download_if_changed() {
+    let (url, requests) = mock_server_recording(vec![http_response("200 OK", "module data")]);
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+
+    let unchanged = client.download_if_changed(&modinfo, &modinfo.md5.to_uppercase()).unwrap();
+    assert_eq!(unchanged, None);
+    assert!(requests.try_recv().is_err());
+
+    let changed = client.download_if_changed(&modinfo, "00000000000000000000000000000000").unwrap();
+    assert_eq!(changed.as_deref(), Some(&b"module data"[..]));
+    assert!(requests.recv().unwrap().starts_with("GET /downloads.php?moduleid=61772 "));
+}
+
+#[test]
+fn mocked_download_results() {
+    // the module info matches what the download server sends
+    let body = include_str!("../fixtures/view_by_moduleid.xml")
+        .replace("5a6b1e4d0b2c9f8e7d6c5b4a39281706", &ModInfo::md5_of(b"module data"))
+        .replace("<bytes>493630</bytes>", "<bytes>11</bytes>");
+    let api = mock_server(
+        vec![
+            http_response("200 OK", &body),
+            http_response("200 OK", include_str!("../fixtures/not_found.xml")),
+            http_response("200 OK", &body)
+        ]
+    );
+    let (downloads, requests) = mock_server_recording(vec![http_response("200 OK", "module data")]);
+    let client = Client::new("mock-key").with_base_url(api).with_download_url(format!("{}/downloads.php", downloads));
+    let dir = env::temp_dir().join("modark_download_results");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let result = |id: u32| crate::ModSearchResolve { id, filename: "7th_dance.xm".into(), title: String::new() };
+    let paths = client.download_results(&[result(61772), result(99999)], &dir);
+    assert_eq!(paths[0].as_ref().unwrap(), &dir.join("61772_7th_dance.xm"));
+    assert_eq!(std::fs::read(dir.join("61772_7th_dance.xm")).unwrap(), b"module data");
+    // one failing doesn't stop the batch
+    assert!(matches!(paths[1], Err(crate::Error::NotFound)));
+    requests.recv().unwrap();
+
+    // it's already there with the right hash, so it isn't downloaded again
+    let paths = client.download_results(&[result(61772)], &dir);
+    assert_eq!(paths[0].as_ref().unwrap(), &dir.join("61772_7th_dance.xm"));
+    assert!(requests.try_recv().is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn mocked_download_results_over_limit() {
+    let body = include_str!("../fixtures/view_by_moduleid.xml");
+    let small = body.replace("<id>61772</id>", "<id>61773</id>");
+    let api = mock_server(vec![http_response("200 OK", body), http_response("200 OK", &small)]);
+    let downloads = mock_server(
+        vec![http_response("200 OK", "Extended Module: 7th Dance"), http_response("200 OK", "module")]
+    );
+    let client = Client::new("mock-key").with_base_url(api).with_download_url(format!("{}/downloads.php", downloads));
+    let dir = env::temp_dir().join("modark_download_results_over_limit");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let result = |id: u32| crate::ModSearchResolve { id, filename: "7th_dance.xm".into(), title: String::new() };
+    let paths = client._download_results(&[result(61772), result(61773)], &dir, 10);
+    assert!(matches!(paths[0], Err(crate::Error::SizeLimitExceeded(10))));
+    assert!(!dir.join("61772_7th_dance.xm").exists());
+    // the one under the limit still gets downloaded
+    assert_eq!(std::fs::read(paths[1].as_ref().unwrap()).unwrap(), b"module");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn mocked_download_redirect() {
+    let redirect = "HTTP/1.1 302 Found\r\nLocation: /cdn/7th_dance.xm\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    let (url, requests) = mock_server_recording(
+        vec![redirect.to_string(), http_response("200 OK", "Extended Module: 7th Dance")]
+    );
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    let bytes = client.download_by_id(61772, "7th_dance.xm").unwrap();
+    assert_eq!(bytes, b"Extended Module: 7th Dance");
+    assert!(requests.recv().unwrap().starts_with("GET /downloads.php?moduleid=61772 "));
+    assert!(requests.recv().unwrap().starts_with("GET /cdn/7th_dance.xm "));
+}
+
+#[test]
+fn mocked_download_error_page() {
+    let page = "\n<!DOCTYPE html><html><body>Module not available</body></html>";
+    let url = mock_server(vec![http_response("200 OK", page), http_response("200 OK", page)]);
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    assert!(matches!(client.download_by_id(61772, "7th_dance.xm"), Err(crate::Error::MalformedResponse)));
+
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    let path = env::temp_dir().join("modark_download_error_page.xm");
+    assert!(matches!(client.download_to(&modinfo, &path), Err(crate::Error::MalformedResponse)));
+    assert!(!path.exists());
+
+    assert!(!crate::looks_like_html(b"Extended Module: 7th Dance"));
+    assert!(!crate::looks_like_html(b""));
+}
+
+#[test]
+fn mocked_download_to_over_limit() {
+    let module = "Extended Module: 7th Dance";
+    let url = mock_server(vec![http_response("200 OK", module), http_response("200 OK", module)]);
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    let modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    let path = env::temp_dir().join("modark_download_to_over_limit.xm");
+
+    let error = client._download_to(&modinfo, &path, 10).unwrap_err();
+    assert!(matches!(error, crate::Error::SizeLimitExceeded(10)));
+    assert!(!path.exists());
+
+    // exactly at the limit is still fine
+    assert_eq!(client._download_to(&modinfo, &path, module.len() as u64).unwrap(), module.len() as u64);
+    assert_eq!(std::fs::read(&path).unwrap(), module.as_bytes());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn mocked_download_resume() {
+    let mut modinfo = ModInfo::from_xml(include_str!("../fixtures/view_by_moduleid.xml"), 61772).unwrap();
+    modinfo.size_bytes = "Extended Module: 7th Dance".len() as u64;
+    let path = env::temp_dir().join("modark_download_resume.xm");
+    let open = || {
+        std::fs::OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path).unwrap()
+    };
+
+    // the server sends only the missing part
+    std::fs::write(&path, "Extended Module: ").unwrap();
+    let rest = "HTTP/1.1 206 Partial Content\r\nContent-Length: 9\r\nConnection: close\r\n\r\n7th Dance";
+    let (url, requests) = mock_server_recording(vec![rest.to_string()]);
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    client.download_resume(&modinfo, &mut open()).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"Extended Module: 7th Dance");
+    assert!(requests.recv().unwrap().to_lowercase().contains("range: bytes=17-"));
+
+    // the server ignores the range and sends all of it
+    std::fs::write(&path, "Extended Module: ").unwrap();
+    let url = mock_server(vec![http_response("200 OK", "Extended Module: 7th Dance")]);
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    client.download_resume(&modinfo, &mut open()).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"Extended Module: 7th Dance");
+
+    // already complete, nothing gets requested
+    client.download_resume(&modinfo, &mut open()).unwrap();
+
+    // the server runs out before the file is complete
+    std::fs::write(&path, "Extended").unwrap();
+    let short = "HTTP/1.1 206 Partial Content\r\nContent-Length: 8\r\nConnection: close\r\n\r\n Module:";
+    let url = mock_server(vec![short.to_string()]);
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    let error = client.download_resume(&modinfo, &mut open()).unwrap_err();
+    assert!(matches!(error, crate::Error::IOError(e) if e.kind() == std::io::ErrorKind::UnexpectedEof));
+
+    // the rest is more than the limit allows for the whole module
+    std::fs::write(&path, "Extended").unwrap();
+    let rest = "HTTP/1.1 206 Partial Content\r\nContent-Length: 18\r\nConnection: close\r\n\r\n Module: 7th Dance";
+    let url = mock_server(vec![rest.to_string()]);
+    let client = Client::default().with_download_url(format!("{}/downloads.php", url));
+    let error = client._download_resume(&modinfo, &mut open(), 20).unwrap_err();
+    assert!(matches!(error, crate::Error::SizeLimitExceeded(20)));
+    assert_eq!(std::fs::read(&path).unwrap(), b"Extended");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_page_limit() {
+    let page = "<html><body>search results</body></html>";
+    assert_eq!(crate::read_page(page.as_bytes(), 1000).unwrap(), page);
+
+    let huge = vec![b'a'; 20000];
+    assert!(matches!(crate::read_page(&huge[..], 10000), Err(crate::Error::SizeLimitExceeded(10000))));
+    assert!(matches!(crate::read_page(&[0xffu8, 0xfe][..], 1000), Err(crate::Error::IOError(_))));
+}
+
+#[test]
+fn read_module_progress() {
+    let data = vec![7u8; 20000];
+    let mut calls = Vec::new();
+    let bytes = crate::read_module(&data[..], 64_000, Some(20000), |read, total| {
+        calls.push((read, total));
+    }).unwrap();
+    assert_eq!(bytes, data);
+    assert_eq!(calls.last(), Some(&(20000, Some(20000))));
+    assert!(calls.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+    let over_limit = crate::read_module(&data[..], 100, None, |_, _| {});
+    assert!(matches!(over_limit, Err(crate::Error::SizeLimitExceeded(100))));
+}
+
+#[test]
+fn download_to_file() {
+    let modinfo = ModInfo::get(
+        41070,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    ).unwrap();
+    let path = env::temp_dir().join("modark_download_to_file.mod");
+    let written = modinfo.download_to(&path).unwrap();
+    assert!(written > 0);
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), written);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn download_with_checksum() {
+    let mut modinfo = ModInfo::get(
+        41070,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    ).unwrap();
+    assert!(modinfo.download_verified().is_ok());
+
+    modinfo.md5 = "00000000000000000000000000000000".into();
+    assert!(
+        matches!(modinfo.download_verified(), Err(crate::Error::ChecksumMismatch { .. }))
+    );
+}
+
+#[test]
+fn download_over_limit() {
+    let modinfo = ModInfo::get(
+        41070,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    ).unwrap();
+    assert!(
+        matches!(modinfo.download_module_with_limit(16), Err(crate::Error::SizeLimitExceeded(16)))
+    );
+}
+
+#[test]
+fn try_from_id() {
+    env::var("MODARCH_KEY").expect("Expected a Mod Archive API key in the environment variables");
+    let modinfo: ModInfo = 61772.try_into().unwrap();
+    assert_eq!(modinfo.id, 61772);
+    assert_eq!(modinfo.filename, "7th_dance.xm");
+}
+
+#[test]
+fn download_follows_to_module() {
+    let modinfo = ModInfo::get(
+        41070,
+        &env
+            ::var("MODARCH_KEY")
+            .expect("Expected a Mod Archive API key in the environment variables")
+    ).unwrap();
+    // a redirect or error page left unfollowed would be far smaller than the module
+    let bytes = modinfo.download_module().unwrap();
+    assert_eq!(bytes.len() as u64, modinfo.size_bytes);
+}
+
+#[test]
+fn download_by_id() {
+    let bytes = ModInfo::download_by_id(41070, "fading_horizont.mod").unwrap();
+    assert!(!bytes.is_empty());
+
+    let result = crate::ModSearchResolve {
+        id: 41070,
+        filename: "fading_horizont.mod".into(),
+        title: "fading horizont".into(),
+    };
+    assert_eq!(result.download().unwrap(), bytes);
+}
+
 #[test]
 fn dl_link_modinfo() {
     let modinfo = ModInfo::get(
@@ -70,3 +1964,64 @@ fn dl_link_modinfo() {
         "https://api.modarchive.org/downloads.php?moduleid=41070#fading_horizont.mod"
     );
 }
+
+/// A module to use in tests which don't need to talk to Mod Archive
+#[cfg(feature = "serde")]
+fn sample_modinfo() -> ModInfo {
+    ModInfo {
+        id: 41070,
+        filename: "fading_horizont.mod".into(),
+        title: "fading horizont".into(),
+        size: "121.52KB".into(),
+        size_bytes: 124436,
+        md5: "2e0f65e3bd4a5bcb8c3c80b4e0b302b4".into(),
+        format: "MOD".into(),
+        spotlit: false,
+        download_count: 1234,
+        fav_count: 5,
+        scrape_time: "2024-07-14T12:00:00+00:00".into(),
+        channel_count: 4,
+        genre: "Electronic - Ambient".into(),
+        upload_date: "Mon 5th Jul 2004".into(),
+        instrument_text: "fading horizont\nby someone".into(),
+        average_rating: Some(7.5),
+        review_count: 1,
+        artist: None,
+        artist_id: None,
+        uploader: None,
+        license: None,
+        tags: vec!["chillout".into()],
+        instrument_count: None,
+        sample_count: Some(15),
+        pattern_count: Some(12),
+        order_count: None,
+        raw_download_count: Some(1234),
+        raw_fav_count: Some(5),
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let modinfo = sample_modinfo();
+    let json = serde_json::to_string(&modinfo).unwrap();
+    let deserialized: ModInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+    assert_eq!(deserialized, modinfo);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_file_round_trip() {
+    let modinfo = sample_modinfo();
+    let path = env::temp_dir().join("modark_json_round_trip.json");
+    modinfo.save_json(&path).unwrap();
+    let loaded = ModInfo::load_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, modinfo);
+
+    std::fs::write(&path, "{ not json").unwrap();
+    let error = ModInfo::load_json(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(error, crate::Error::SerializationError(_)));
+}